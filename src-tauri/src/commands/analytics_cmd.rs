@@ -0,0 +1,14 @@
+//! 写作分析相关 Tauri Commands
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::analytics::*;
+use crate::db::analytics_dao;
+
+/// 按日期范围和粒度统计写作数据（含与上一周期的比较）
+#[tauri::command]
+pub fn get_writing_analytics(db: State<'_, DbState>, request: AnalyticsRequest) -> AppResult<WritingAnalytics> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    analytics_dao::get_writing_analytics(&conn, &request)
+}
@@ -0,0 +1,35 @@
+//! 写作草稿相关 Tauri Commands
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::draft::*;
+use crate::db::draft_dao;
+
+/// 自动保存草稿（正文为空时删除草稿并返回 null）
+#[tauri::command]
+pub fn save_draft(db: State<'_, DbState>, request: SaveDraftRequest) -> AppResult<Option<Draft>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    draft_dao::save_draft(&conn, &request)
+}
+
+/// 获取所有草稿（启动时用于恢复未保存的写作）
+#[tauri::command]
+pub fn get_drafts(db: State<'_, DbState>) -> AppResult<Vec<Draft>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    draft_dao::get_drafts(&conn)
+}
+
+/// 获取计划日条目（或自由写作）对应的草稿
+#[tauri::command]
+pub fn get_draft(db: State<'_, DbState>, plan_day_id: Option<i64>) -> AppResult<Option<Draft>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    draft_dao::get_draft(&conn, plan_day_id)
+}
+
+/// 丢弃草稿
+#[tauri::command]
+pub fn discard_draft(db: State<'_, DbState>, id: i64) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    draft_dao::discard_draft(&conn, id)
+}
@@ -0,0 +1,35 @@
+//! 写作目标相关 Tauri Commands
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::goal::*;
+use crate::db::goal_dao;
+
+/// 获取所有写作目标
+#[tauri::command]
+pub fn get_writing_goals(db: State<'_, DbState>) -> AppResult<Vec<WritingGoal>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    goal_dao::get_goals(&conn)
+}
+
+/// 设置写作目标（同周期同指标已存在时更新）
+#[tauri::command]
+pub fn set_writing_goal(db: State<'_, DbState>, request: SetWritingGoalRequest) -> AppResult<WritingGoal> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    goal_dao::set_goal(&conn, &request)
+}
+
+/// 删除写作目标
+#[tauri::command]
+pub fn delete_writing_goal(db: State<'_, DbState>, id: i64) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    goal_dao::delete_goal(&conn, id)
+}
+
+/// 获取目标在当前周期（或指定日期所在周期）内的进度
+#[tauri::command]
+pub fn get_goal_progress(db: State<'_, DbState>, date: Option<String>) -> AppResult<Vec<GoalProgress>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    goal_dao::get_goal_progress(&conn, date.as_deref())
+}
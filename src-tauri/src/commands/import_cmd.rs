@@ -7,21 +7,32 @@ use crate::commands::task_cmd::DbState;
 use crate::errors::{AppError, AppResult};
 use crate::models::task::*;
 use crate::models::plan::*;
-use crate::db::{task_dao, plan_dao};
+use crate::db::{task_dao, task_list_dao, plan_dao};
 
 /// 从 JSON 字符串批量导入任务
+///
+/// 指定 `list_name` 时导入到该列表，列表不存在则自动创建。
 #[tauri::command]
-pub fn import_tasks_json(db: State<'_, DbState>, json_content: String) -> AppResult<Vec<Task>> {
+pub fn import_tasks_json(db: State<'_, DbState>, json_content: String, list_name: Option<String>) -> AppResult<Vec<Task>> {
     let conn = db.lock().map_err(|e| AppError::Business(e.to_string()))?;
 
     let items: Vec<ImportTaskItem> = serde_json::from_str(&json_content)?;
+    let list_id = match list_name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => Some(task_list_dao::find_or_create_task_list(&conn, name)?),
+        _ => None,
+    };
     let requests: Vec<CreateTaskRequest> = items.into_iter().map(|item| {
         CreateTaskRequest {
             title: item.title,
             description: item.description,
             priority: item.priority,
             due_date: item.due_date.or(item.due),
+            due_time: item.due_time,
             repeat: item.repeat,
+            rrule: item.rrule,
+            exdates: Vec::new(),
+            auto_complete: false,
+            list_id,
         }
     }).collect();
 
@@ -127,6 +138,8 @@ fn parse_markdown_plan(content: &str, start_date: &str) -> AppResult<ImportPlanR
         theme: None,
         start_date: start_date.to_string(),
         days,
+        writing_weekdays: Vec::new(),
+        excluded_dates: Vec::new(),
     })
 }
 
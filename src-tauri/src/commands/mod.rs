@@ -6,3 +6,11 @@ pub mod task_cmd;
 pub mod plan_cmd;
 pub mod writing_cmd;
 pub mod import_cmd;
+pub mod settings_cmd;
+pub mod tag_cmd;
+pub mod task_list_cmd;
+pub mod search_cmd;
+pub mod draft_cmd;
+pub mod revision_cmd;
+pub mod goal_cmd;
+pub mod analytics_cmd;
@@ -1,12 +1,25 @@
 //! 写作计划相关 Tauri Commands
 
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use crate::commands::task_cmd::DbState;
 use crate::errors::AppResult;
 use crate::models::plan::*;
-use crate::db::plan_dao;
+use crate::models::plan_event::*;
+use crate::db::{plan_dao, plan_event_dao};
 use crate::plan_generator;
 
+/// 计划自动完成时发给前端的事件名
+pub const PLAN_COMPLETED_EVENT: &str = "plan-completed";
+
+/// 向前端发送计划完成事件
+pub(crate) fn emit_plan_completed(app: &AppHandle, completed: &[PlanCompletedPayload]) {
+    for payload in completed {
+        if let Err(e) = app.emit(PLAN_COMPLETED_EVENT, payload) {
+            log::warn!("计划完成事件发送失败: {}", e);
+        }
+    }
+}
+
 /// 获取所有写作计划
 #[tauri::command]
 pub fn get_plans(db: State<'_, DbState>) -> AppResult<Vec<WritingPlan>> {
@@ -47,18 +60,32 @@ pub fn generate_plan(db: State<'_, DbState>, request: GeneratePlanRequest) -> Ap
     plan_dao::create_plan(&conn, &import_req)
 }
 
-/// 更新计划基本信息（名称/主题/日期）
+/// 更新计划基本信息（名称/主题/日期），可选重排未完成条目，返回计划详情
 #[tauri::command]
-pub fn update_plan(db: State<'_, DbState>, request: UpdatePlanRequest) -> AppResult<WritingPlan> {
+pub fn update_plan(db: State<'_, DbState>, request: UpdatePlanRequest) -> AppResult<PlanWithDays> {
     let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
     plan_dao::update_plan(&conn, &request)
 }
 
+/// 补救错过的计划日（顺延 / 移到末尾 / 跳过）
+#[tauri::command]
+pub fn catch_up_plan(db: State<'_, DbState>, request: CatchUpPlanRequest) -> AppResult<CatchUpResult> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    plan_dao::catch_up_plan(&conn, &request, chrono::Local::now().date_naive())
+}
+
 /// 更新计划状态
 #[tauri::command]
 pub fn update_plan_status(db: State<'_, DbState>, plan_id: i64, status: String) -> AppResult<()> {
     let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
-    plan_dao::update_plan_status(&conn, plan_id, PlanStatus::from_str(&status))
+    plan_dao::update_plan_status(&conn, plan_id, PlanStatus::from_str(&status), chrono::Local::now().naive_local())
+}
+
+/// 获取计划的生命周期历史
+#[tauri::command]
+pub fn get_plan_events(db: State<'_, DbState>, plan_id: i64) -> AppResult<Vec<PlanEvent>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    plan_event_dao::get_plan_events(&conn, plan_id)
 }
 
 /// 删除计划
@@ -0,0 +1,36 @@
+//! 写作修订历史相关 Tauri Commands
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::revision::*;
+use crate::models::writing::Writing;
+use crate::db::revision_dao;
+
+/// 获取写作记录的所有版本
+#[tauri::command]
+pub fn get_writing_revisions(db: State<'_, DbState>, writing_id: i64) -> AppResult<Vec<WritingRevision>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    revision_dao::get_revisions(&conn, writing_id)
+}
+
+/// 获取单个版本
+#[tauri::command]
+pub fn get_writing_revision(db: State<'_, DbState>, id: i64) -> AppResult<WritingRevision> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    revision_dao::get_revision_by_id(&conn, id)
+}
+
+/// 比较两个版本
+#[tauri::command]
+pub fn diff_writing_revisions(db: State<'_, DbState>, request: DiffRevisionsRequest) -> AppResult<RevisionDiff> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    revision_dao::diff_revisions(&conn, &request)
+}
+
+/// 恢复旧版本为当前内容
+#[tauri::command]
+pub fn restore_writing_revision(db: State<'_, DbState>, id: i64) -> AppResult<Writing> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    revision_dao::restore_revision(&conn, id)
+}
@@ -0,0 +1,14 @@
+//! 全文搜索 Tauri Command
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::search::*;
+use crate::db::search_dao;
+
+/// 搜索写作记录、每日题目和任务
+#[tauri::command]
+pub fn search(db: State<'_, DbState>, request: SearchRequest) -> AppResult<Vec<SearchResult>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    search_dao::search(&conn, &request)
+}
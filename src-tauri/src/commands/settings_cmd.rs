@@ -0,0 +1,49 @@
+//! 应用设置相关 Tauri Commands
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::settings::*;
+use crate::db::settings_dao;
+
+/// 获取每日写作提醒设置
+#[tauri::command]
+pub fn get_writing_reminder_settings(db: State<'_, DbState>) -> AppResult<WritingReminderSettings> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    settings_dao::get_writing_reminder_settings(&conn)
+}
+
+/// 保存每日写作提醒设置
+#[tauri::command]
+pub fn update_writing_reminder_settings(db: State<'_, DbState>, settings: WritingReminderSettings) -> AppResult<WritingReminderSettings> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    settings_dao::save_writing_reminder_settings(&conn, &settings)
+}
+
+/// 获取连续写作统计方式
+#[tauri::command]
+pub fn get_streak_settings(db: State<'_, DbState>) -> AppResult<StreakSettings> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    settings_dao::get_streak_settings(&conn)
+}
+
+/// 保存连续写作统计方式
+#[tauri::command]
+pub fn update_streak_settings(db: State<'_, DbState>, settings: StreakSettings) -> AppResult<StreakSettings> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    settings_dao::save_streak_settings(&conn, &settings)
+}
+
+/// 获取计划自动完成设置
+#[tauri::command]
+pub fn get_plan_completion_settings(db: State<'_, DbState>) -> AppResult<PlanCompletionSettings> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    settings_dao::get_plan_completion_settings(&conn)
+}
+
+/// 保存计划自动完成设置
+#[tauri::command]
+pub fn update_plan_completion_settings(db: State<'_, DbState>, settings: PlanCompletionSettings) -> AppResult<PlanCompletionSettings> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    settings_dao::save_plan_completion_settings(&conn, &settings)
+}
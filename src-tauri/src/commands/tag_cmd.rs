@@ -0,0 +1,56 @@
+//! 标签相关 Tauri Commands
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::tag::*;
+use crate::db::tag_dao;
+
+/// 获取所有标签
+#[tauri::command]
+pub fn get_tags(db: State<'_, DbState>) -> AppResult<Vec<Tag>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    tag_dao::get_all_tags(&conn)
+}
+
+/// 创建标签
+#[tauri::command]
+pub fn create_tag(db: State<'_, DbState>, request: CreateTagRequest) -> AppResult<Tag> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    tag_dao::create_tag(&conn, &request)
+}
+
+/// 更新标签
+#[tauri::command]
+pub fn update_tag(db: State<'_, DbState>, request: UpdateTagRequest) -> AppResult<Tag> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    tag_dao::update_tag(&conn, &request)
+}
+
+/// 删除标签
+#[tauri::command]
+pub fn delete_tag(db: State<'_, DbState>, id: i64) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    tag_dao::delete_tag(&conn, id)
+}
+
+/// 设置任务标签
+#[tauri::command]
+pub fn set_task_tags(db: State<'_, DbState>, request: SetTagsRequest) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    tag_dao::set_task_tags(&conn, &request)
+}
+
+/// 设置写作记录标签
+#[tauri::command]
+pub fn set_writing_tags(db: State<'_, DbState>, request: SetTagsRequest) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    tag_dao::set_writing_tags(&conn, &request)
+}
+
+/// 获取标签使用统计
+#[tauri::command]
+pub fn get_tag_stats(db: State<'_, DbState>) -> AppResult<Vec<TagStats>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    tag_dao::get_tag_stats(&conn)
+}
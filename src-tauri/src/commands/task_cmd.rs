@@ -5,16 +5,20 @@ use std::sync::Mutex;
 use rusqlite::Connection;
 use crate::errors::AppResult;
 use crate::models::task::*;
-use crate::db::task_dao;
+use crate::models::task_event::*;
+use crate::db::{task_dao, task_event_dao};
 
 /// 数据库连接状态
 pub type DbState = Mutex<Connection>;
 
-/// 获取所有任务
+/// 获取所有任务（可按标签过滤，包含任一标签即匹配）
 #[tauri::command]
-pub fn get_tasks(db: State<'_, DbState>) -> AppResult<Vec<Task>> {
+pub fn get_tasks(db: State<'_, DbState>, tag_ids: Option<Vec<i64>>) -> AppResult<Vec<Task>> {
     let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
-    task_dao::get_all_tasks(&conn)
+    match tag_ids {
+        Some(ids) if !ids.is_empty() => task_dao::get_tasks_by_tags(&conn, &ids),
+        _ => task_dao::get_all_tasks(&conn),
+    }
 }
 
 /// 获取今日待办
@@ -24,6 +28,27 @@ pub fn get_today_tasks(db: State<'_, DbState>) -> AppResult<Vec<Task>> {
     task_dao::get_today_tasks(&conn)
 }
 
+/// 按条件查询任务（筛选、排序、分页）
+#[tauri::command]
+pub fn query_tasks(db: State<'_, DbState>, request: Option<TaskQuery>) -> AppResult<TaskPage> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::query_tasks(&conn, &request.unwrap_or_default())
+}
+
+/// 获取按截止日期分组的任务：逾期、今天、明天、本周、以后、无日期
+#[tauri::command]
+pub fn get_task_buckets(db: State<'_, DbState>, include_completed: Option<bool>) -> AppResult<TaskBuckets> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::get_task_buckets(&conn, chrono::Local::now().date_naive(), include_completed.unwrap_or(false))
+}
+
+/// 把逾期任务顺延到今天
+#[tauri::command]
+pub fn roll_over_overdue_tasks(db: State<'_, DbState>, request: Option<RollOverTasksRequest>) -> AppResult<Vec<Task>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::roll_over_overdue(&conn, chrono::Local::now().date_naive(), &request.unwrap_or_default())
+}
+
 /// 创建任务
 #[tauri::command]
 pub fn create_task(db: State<'_, DbState>, request: CreateTaskRequest) -> AppResult<Task> {
@@ -51,3 +76,86 @@ pub fn toggle_task(db: State<'_, DbState>, id: i64) -> AppResult<Task> {
     let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
     task_dao::toggle_task(&conn, id)
 }
+
+/// 获取重复任务的完成历史
+#[tauri::command]
+pub fn get_task_completions(db: State<'_, DbState>, task_id: i64) -> AppResult<Vec<TaskCompletion>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::get_task_completions(&conn, task_id)
+}
+
+/// 获取任务的事件历史（创建、完成、重新打开、改期）
+#[tauri::command]
+pub fn get_task_events(db: State<'_, DbState>, task_id: i64) -> AppResult<Vec<TaskEvent>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_event_dao::get_task_events(&conn, task_id)
+}
+
+/// 获取任务完成统计（不传请求时为最近 30 天）
+#[tauri::command]
+pub fn get_task_stats(db: State<'_, DbState>, request: Option<TaskStatsRequest>) -> AppResult<TaskStats> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_event_dao::get_task_stats(&conn, &request.unwrap_or_default())
+}
+
+/// 预览重复规则接下来的发生日期
+#[tauri::command]
+pub fn preview_rrule(request: PreviewRRuleRequest) -> AppResult<Vec<String>> {
+    task_dao::preview_rrule(&request)
+}
+
+/// 获取任务的提醒设置
+#[tauri::command]
+pub fn get_task_reminders(db: State<'_, DbState>, task_id: i64) -> AppResult<Vec<TaskReminder>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::get_task_reminders(&conn, task_id)
+}
+
+/// 设置任务的提醒（整体替换）
+#[tauri::command]
+pub fn set_task_reminders(db: State<'_, DbState>, request: SetTaskRemindersRequest) -> AppResult<Vec<TaskReminder>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::set_task_reminders(&conn, &request)
+}
+
+/// 获取任务详情（含清单项）
+#[tauri::command]
+pub fn get_task_detail(db: State<'_, DbState>, id: i64) -> AppResult<TaskDetail> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::get_task_detail(&conn, id)
+}
+
+/// 添加清单项
+#[tauri::command]
+pub fn add_task_item(db: State<'_, DbState>, request: AddTaskItemRequest) -> AppResult<TaskDetail> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::add_task_item(&conn, &request)
+}
+
+/// 更新清单项
+#[tauri::command]
+pub fn update_task_item(db: State<'_, DbState>, request: UpdateTaskItemRequest) -> AppResult<TaskDetail> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::update_task_item(&conn, &request)
+}
+
+/// 切换清单项完成状态
+#[tauri::command]
+pub fn toggle_task_item(db: State<'_, DbState>, id: i64) -> AppResult<TaskDetail> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::toggle_task_item(&conn, id)
+}
+
+/// 删除清单项
+#[tauri::command]
+pub fn delete_task_item(db: State<'_, DbState>, id: i64) -> AppResult<TaskDetail> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::delete_task_item(&conn, id)
+}
+
+/// 清单项重新排序
+#[tauri::command]
+pub fn reorder_task_items(db: State<'_, DbState>, request: ReorderTaskItemsRequest) -> AppResult<TaskDetail> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::reorder_task_items(&conn, &request)
+}
@@ -0,0 +1,57 @@
+//! 任务列表（项目）相关 Tauri Commands
+
+use tauri::State;
+use crate::commands::task_cmd::DbState;
+use crate::errors::AppResult;
+use crate::models::task::Task;
+use crate::models::task_list::*;
+use crate::db::{task_dao, task_list_dao};
+
+/// 获取任务列表（`include_archived` 为 true 时包含已归档列表）
+#[tauri::command]
+pub fn get_task_lists(db: State<'_, DbState>, include_archived: Option<bool>) -> AppResult<Vec<TaskList>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_list_dao::get_task_lists(&conn, include_archived.unwrap_or(false))
+}
+
+/// 创建任务列表
+#[tauri::command]
+pub fn create_task_list(db: State<'_, DbState>, request: CreateTaskListRequest) -> AppResult<TaskList> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_list_dao::create_task_list(&conn, &request)
+}
+
+/// 更新任务列表（含归档 / 取消归档）
+#[tauri::command]
+pub fn update_task_list(db: State<'_, DbState>, request: UpdateTaskListRequest) -> AppResult<TaskList> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_list_dao::update_task_list(&conn, &request)
+}
+
+/// 删除任务列表（任务移回收件箱）
+#[tauri::command]
+pub fn delete_task_list(db: State<'_, DbState>, id: i64) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_list_dao::delete_task_list(&conn, id)
+}
+
+/// 重排任务列表
+#[tauri::command]
+pub fn reorder_task_lists(db: State<'_, DbState>, request: ReorderTaskListsRequest) -> AppResult<Vec<TaskList>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_list_dao::reorder_task_lists(&conn, &request)
+}
+
+/// 获取列表中的任务（`list_id` 为空时返回收件箱）
+#[tauri::command]
+pub fn get_list_tasks(db: State<'_, DbState>, list_id: Option<i64>) -> AppResult<Vec<Task>> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_dao::get_tasks_by_list(&conn, list_id)
+}
+
+/// 在列表之间移动任务
+#[tauri::command]
+pub fn move_tasks(db: State<'_, DbState>, request: MoveTasksRequest) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    task_list_dao::move_tasks(&conn, &request)
+}
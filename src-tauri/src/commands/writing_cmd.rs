@@ -1,16 +1,67 @@
 //! 写作记录相关 Tauri Commands
 
-use tauri::State;
+use tauri::{AppHandle, State};
+use crate::commands::plan_cmd::emit_plan_completed;
 use crate::commands::task_cmd::DbState;
 use crate::errors::AppResult;
 use crate::models::writing::*;
-use crate::db::writing_dao;
+use crate::models::streak::StreakSummary;
+use crate::models::heatmap::*;
+use crate::db::{heatmap_dao, plan_dao, writing_dao};
+use crate::text_metrics;
 
-/// 保存写作记录
+/// 保存写作记录（关联计划日时检查计划是否已完成）
 #[tauri::command]
-pub fn save_writing(db: State<'_, DbState>, request: SaveWritingRequest) -> AppResult<Writing> {
+pub fn save_writing(app: AppHandle, db: State<'_, DbState>, request: SaveWritingRequest) -> AppResult<Writing> {
     let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
-    writing_dao::save_writing(&conn, &request)
+    let writing = writing_dao::save_writing(&conn, &request)?;
+    if request.plan_day_id.is_some() {
+        let completed = plan_dao::auto_complete_plans(&conn, chrono::Local::now().naive_local())?;
+        emit_plan_completed(&app, &completed);
+    }
+    Ok(writing)
+}
+
+/// 更新写作记录（关联到计划日时检查计划是否已完成，离开原计划日时重新检查原计划）
+#[tauri::command]
+pub fn update_writing(app: AppHandle, db: State<'_, DbState>, request: UpdateWritingRequest) -> AppResult<Writing> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    let previous_day = writing_dao::get_writing_by_id(&conn, request.id)?.plan_day_id;
+    let writing = writing_dao::update_writing(&conn, &request)?;
+    let now = chrono::Local::now().naive_local();
+    if let Some(day) = previous_day.filter(|day| writing.plan_day_id != Some(*day)) {
+        plan_dao::recheck_completion(&conn, day, now)?;
+    }
+    if request.plan_day_id.is_some() && !request.clear_plan_day {
+        let completed = plan_dao::auto_complete_plans(&conn, now)?;
+        emit_plan_completed(&app, &completed);
+    }
+    Ok(writing)
+}
+
+/// 删除写作记录（关联了计划日时重新检查计划是否仍然完成）
+#[tauri::command]
+pub fn delete_writing(db: State<'_, DbState>, id: i64) -> AppResult<()> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    let plan_day_id = writing_dao::get_writing_by_id(&conn, id)?.plan_day_id;
+    writing_dao::delete_writing(&conn, id)?;
+    if let Some(day) = plan_day_id {
+        plan_dao::recheck_completion(&conn, day, chrono::Local::now().naive_local())?;
+    }
+    Ok(())
+}
+
+/// 统计编辑器中的正文（与保存时的统计规则一致，供编辑器实时显示字数）
+#[tauri::command]
+pub fn analyze_writing_text(content: String) -> TextMetrics {
+    text_metrics::analyze_html(&content)
+}
+
+/// 按当前统计规则重新计算历史写作记录的字数
+#[tauri::command]
+pub fn recompute_writing_metrics(db: State<'_, DbState>) -> AppResult<RecomputeMetricsResult> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    writing_dao::recompute_metrics(&conn)
 }
 
 /// 获取写作记录列表
@@ -24,14 +75,21 @@ pub fn get_writings(db: State<'_, DbState>, filter: WritingFilter) -> AppResult<
 #[tauri::command]
 pub fn get_writing_stats(db: State<'_, DbState>) -> AppResult<WritingStats> {
     let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
-    writing_dao::get_writing_stats(&conn)
+    writing_dao::get_writing_stats(&conn, chrono::Local::now().date_naive())
+}
+
+/// 获取连续写作统计（当前连续、最长连续及其日期、所有中断）
+#[tauri::command]
+pub fn get_streak_summary(db: State<'_, DbState>) -> AppResult<StreakSummary> {
+    let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
+    writing_dao::get_streak_summary(&conn, chrono::Local::now().date_naive())
 }
 
-/// 获取热力图数据
+/// 获取热力图数据（不传请求时为最近一年的写作字数）
 #[tauri::command]
-pub fn get_heatmap(db: State<'_, DbState>) -> AppResult<Vec<HeatmapEntry>> {
+pub fn get_heatmap(db: State<'_, DbState>, request: Option<HeatmapRequest>) -> AppResult<Heatmap> {
     let conn = db.lock().map_err(|e| crate::errors::AppError::Business(e.to_string()))?;
-    writing_dao::get_heatmap_data(&conn)
+    heatmap_dao::get_heatmap(&conn, &request.unwrap_or_default())
 }
 
 /// 获取单篇写作记录详情
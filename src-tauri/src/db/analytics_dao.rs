@@ -0,0 +1,165 @@
+//! 写作分析数据库操作
+
+use rusqlite::{params, Connection};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use crate::errors::{AppError, AppResult};
+use crate::models::analytics::*;
+
+/// 单次分析最多返回的区间数
+const MAX_BUCKETS: usize = 1_000;
+
+/// 单日写作汇总
+struct DayRow {
+    date: NaiveDate,
+    words: i64,
+    sessions: i64,
+    duration_seconds: i64,
+    /// 有时长记录的写作字数（用于计算每分钟字数）
+    timed_words: i64,
+    plan_words: i64,
+    plan_sessions: i64,
+}
+
+/// 按日期范围和粒度统计写作数据，并与上一周期比较
+pub fn get_writing_analytics(conn: &Connection, req: &AnalyticsRequest) -> AppResult<WritingAnalytics> {
+    let start = parse_date(&req.start_date)?;
+    let end = parse_date(&req.end_date)?;
+    if end < start {
+        return Err(AppError::Business("结束日期不能早于开始日期".to_string()));
+    }
+
+    let ranges = bucket_ranges(start, end, req.granularity)?;
+    let days = get_day_rows(conn, start, end)?;
+    let buckets: Vec<AnalyticsBucket> = ranges.into_iter().map(|(s, e)| AnalyticsBucket {
+        start: s,
+        end: e,
+        totals: summarize(days.iter().filter(|d| d.date >= s && d.date <= e)),
+    }).collect();
+    let totals = summarize(days.iter());
+
+    let previous_end = start - Duration::days(1);
+    let previous_start = previous_end - (end - start);
+    let previous_days = get_day_rows(conn, previous_start, previous_end)?;
+    let previous_totals = summarize(previous_days.iter());
+
+    let change = AnalyticsChange {
+        words: percent_change(totals.words as f64, previous_totals.words as f64),
+        sessions: percent_change(totals.sessions as f64, previous_totals.sessions as f64),
+        duration_seconds: percent_change(totals.duration_seconds as f64, previous_totals.duration_seconds as f64),
+        words_per_minute: percent_change(totals.words_per_minute, previous_totals.words_per_minute),
+        active_days: percent_change(totals.active_days as f64, previous_totals.active_days as f64),
+    };
+
+    Ok(WritingAnalytics {
+        start_date: start,
+        end_date: end,
+        granularity: req.granularity,
+        buckets,
+        totals,
+        previous_start_date: previous_start,
+        previous_end_date: previous_end,
+        previous_totals,
+        change,
+    })
+}
+
+fn parse_date(s: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)))
+}
+
+/// 读取范围内每天的写作汇总
+fn get_day_rows(conn: &Connection, start: NaiveDate, end: NaiveDate) -> AppResult<Vec<DayRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT written_date,
+                COALESCE(SUM(word_count), 0),
+                COUNT(*),
+                COALESCE(SUM(duration_seconds), 0),
+                COALESCE(SUM(CASE WHEN duration_seconds > 0 THEN word_count ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN plan_day_id IS NOT NULL THEN word_count ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN plan_day_id IS NOT NULL THEN 1 ELSE 0 END), 0)
+         FROM writings
+         WHERE written_date BETWEEN ?1 AND ?2
+         GROUP BY written_date
+         ORDER BY written_date ASC"
+    )?;
+    let rows = stmt.query_map(
+        params![start.format("%Y-%m-%d").to_string(), end.format("%Y-%m-%d").to_string()],
+        |row| Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?)),
+    )?.collect::<Result<Vec<_>, _>>()?;
+
+    let days = rows.into_iter()
+        .filter_map(|(date, words, sessions, duration_seconds, timed_words, plan_words, plan_sessions)| {
+            Some(DayRow {
+                date: NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()?,
+                words,
+                sessions,
+                duration_seconds,
+                timed_words,
+                plan_words,
+                plan_sessions,
+            })
+        })
+        .collect();
+    Ok(days)
+}
+
+/// 汇总若干天的数据
+fn summarize<'a>(days: impl Iterator<Item = &'a DayRow>) -> AnalyticsTotals {
+    let mut totals = AnalyticsTotals::default();
+    let mut timed_words = 0;
+    for day in days {
+        timed_words += day.timed_words;
+        totals.words += day.words;
+        totals.sessions += day.sessions;
+        totals.duration_seconds += day.duration_seconds;
+        totals.plan_words += day.plan_words;
+        totals.plan_sessions += day.plan_sessions;
+        if day.sessions > 0 {
+            totals.active_days += 1;
+        }
+    }
+    totals.free_words = totals.words - totals.plan_words;
+    totals.free_sessions = totals.sessions - totals.plan_sessions;
+    if totals.duration_seconds > 0 {
+        totals.words_per_minute = timed_words as f64 * 60.0 / totals.duration_seconds as f64;
+    }
+    totals
+}
+
+/// 变化百分比；上一周期为 0 时无法比较
+fn percent_change(current: f64, previous: f64) -> Option<f64> {
+    if previous == 0.0 {
+        None
+    } else {
+        Some((current - previous) * 100.0 / previous)
+    }
+}
+
+/// 把日期范围切分为按粒度对齐的区间，首尾区间截断到范围内
+fn bucket_ranges(start: NaiveDate, end: NaiveDate, granularity: AnalyticsGranularity) -> AppResult<Vec<(NaiveDate, NaiveDate)>> {
+    let mut ranges = Vec::new();
+    let mut cursor = start;
+    while cursor <= end {
+        if ranges.len() >= MAX_BUCKETS {
+            return Err(AppError::Business(format!("统计区间过多（最多 {} 个），请缩小日期范围或加大粒度", MAX_BUCKETS)));
+        }
+        let next = next_bucket_start(cursor, granularity);
+        ranges.push((cursor, (next - Duration::days(1)).min(end)));
+        cursor = next;
+    }
+    Ok(ranges)
+}
+
+/// `date` 所在区间的下一个区间的开始日期
+fn next_bucket_start(date: NaiveDate, granularity: AnalyticsGranularity) -> NaiveDate {
+    match granularity {
+        AnalyticsGranularity::Day => date + Duration::days(1),
+        AnalyticsGranularity::Week => date + Duration::days(7 - date.weekday().num_days_from_monday() as i64),
+        AnalyticsGranularity::Month => {
+            let first = date.with_day(1).unwrap_or(date);
+            first.checked_add_months(Months::new(1)).unwrap_or(NaiveDate::MAX)
+        }
+        AnalyticsGranularity::Year => NaiveDate::from_ymd_opt(date.year() + 1, 1, 1).unwrap_or(NaiveDate::MAX),
+    }
+}
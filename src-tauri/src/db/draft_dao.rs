@@ -0,0 +1,164 @@
+//! 写作草稿数据库操作
+
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::errors::{AppError, AppResult};
+use crate::models::draft::*;
+use crate::text_metrics;
+
+/// 草稿查询的列（表别名为 `d`，顺序与 [`map_draft_row`] 对应）
+const DRAFT_COLUMNS: &str =
+    "d.id, d.plan_day_id, d.title, d.content, d.word_count, d.duration_seconds, d.created_at, d.updated_at,
+     pd.plan_id, pd.title, wp.name";
+
+/// 草稿查询的表连接
+const DRAFT_JOINS: &str =
+    "FROM drafts d
+     LEFT JOIN plan_days pd ON pd.id = d.plan_day_id
+     LEFT JOIN writing_plans wp ON wp.id = pd.plan_id";
+
+/// 保存草稿（同一槽位覆盖），正文为空时删除草稿并返回 `None`
+pub fn save_draft(conn: &Connection, req: &SaveDraftRequest) -> AppResult<Option<Draft>> {
+    let slot = slot_key(req.plan_day_id);
+    let word_count = text_metrics::analyze_html(&req.content).word_count;
+    if word_count == 0 {
+        conn.execute("DELETE FROM drafts WHERE slot = ?1", params![slot])?;
+        return Ok(None);
+    }
+
+    conn.execute(
+        "INSERT INTO drafts (slot, plan_day_id, title, content, word_count, duration_seconds)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(slot) DO UPDATE SET
+            title = excluded.title,
+            content = excluded.content,
+            word_count = excluded.word_count,
+            duration_seconds = excluded.duration_seconds,
+            updated_at = datetime('now', 'localtime')",
+        params![slot, req.plan_day_id, req.title, req.content, word_count, req.duration_seconds],
+    )?;
+
+    get_draft(conn, req.plan_day_id)
+}
+
+/// 获取所有草稿（最近修改的在前）
+pub fn get_drafts(conn: &Connection) -> AppResult<Vec<Draft>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} {} ORDER BY d.updated_at DESC, d.id DESC",
+        DRAFT_COLUMNS, DRAFT_JOINS
+    ))?;
+
+    let drafts = stmt.query_map([], map_draft_row)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(drafts)
+}
+
+/// 获取计划日条目（或自由写作）对应的草稿，用于继续写作
+pub fn get_draft(conn: &Connection, plan_day_id: Option<i64>) -> AppResult<Option<Draft>> {
+    let draft = conn.query_row(
+        &format!("SELECT {} {} WHERE d.slot = ?1", DRAFT_COLUMNS, DRAFT_JOINS),
+        params![slot_key(plan_day_id)],
+        map_draft_row,
+    ).optional()?;
+    Ok(draft)
+}
+
+/// 丢弃草稿
+pub fn discard_draft(conn: &Connection, id: i64) -> AppResult<()> {
+    let affected = conn.execute("DELETE FROM drafts WHERE id = ?1", params![id])?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("草稿 ID {} 不存在", id)));
+    }
+    Ok(())
+}
+
+/// 清除某个槽位的草稿（写作记录正式保存后调用）
+pub fn clear_draft(conn: &Connection, plan_day_id: Option<i64>) -> AppResult<()> {
+    conn.execute("DELETE FROM drafts WHERE slot = ?1", params![slot_key(plan_day_id)])?;
+    Ok(())
+}
+
+/// 草稿槽位：每个计划日条目一个，自由写作共用一个
+///
+/// 计划日条目删除时草稿随外键级联删除（见迁移 v11），槽位不会指向已删除的条目。
+fn slot_key(plan_day_id: Option<i64>) -> String {
+    match plan_day_id {
+        Some(id) => format!("plan_day:{}", id),
+        None => "free".to_string(),
+    }
+}
+
+fn map_draft_row(row: &rusqlite::Row) -> rusqlite::Result<Draft> {
+    Ok(Draft {
+        id: row.get(0)?,
+        plan_day_id: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get(3)?,
+        word_count: row.get(4)?,
+        duration_seconds: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+        plan_id: row.get(8)?,
+        plan_day_title: row.get(9)?,
+        plan_name: row.get(10)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::init_database;
+    use crate::db::plan_dao;
+    use crate::models::plan::ImportPlanRequest;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        conn
+    }
+
+    fn save(conn: &Connection, plan_day_id: Option<i64>, content: &str) -> Option<Draft> {
+        let req: SaveDraftRequest = serde_json::from_value(serde_json::json!({
+            "plan_day_id": plan_day_id, "title": "草稿", "content": content, "duration_seconds": 60,
+        })).unwrap();
+        save_draft(conn, &req).unwrap()
+    }
+
+    fn create_plan(conn: &Connection) -> Vec<i64> {
+        let req: ImportPlanRequest = serde_json::from_str(r#"{"name":"计划","start_date":"2026-10-01","days":[
+            {"day":1,"title":"第一天","prompt":""},{"day":2,"title":"第二天","prompt":""}]}"#).unwrap();
+        let plan_id = plan_dao::create_plan(conn, &req).unwrap();
+        plan_dao::get_plan_with_days(conn, plan_id).unwrap().days.iter().map(|d| d.id).collect()
+    }
+
+    #[test]
+    fn each_slot_keeps_one_draft() {
+        let conn = setup();
+        let days = create_plan(&conn);
+        save(&conn, None, "<p>自由写作</p>");
+        save(&conn, Some(days[0]), "<p>第一版</p>");
+        let draft = save(&conn, Some(days[0]), "<p>第二版</p>").unwrap();
+
+        assert_eq!(draft.content, "<p>第二版</p>");
+        assert_eq!(draft.plan_day_title.as_deref(), Some("第一天"));
+        assert_eq!(get_drafts(&conn).unwrap().len(), 2);
+
+        // 正文清空时删除草稿
+        assert!(save(&conn, Some(days[0]), "<p></p>").is_none());
+        assert!(get_draft(&conn, Some(days[0])).unwrap().is_none());
+        assert!(get_draft(&conn, None).unwrap().is_some());
+    }
+
+    #[test]
+    fn deleting_plan_day_removes_its_draft() {
+        let conn = setup();
+        let days = create_plan(&conn);
+        save(&conn, None, "<p>自由写作</p>");
+        save(&conn, Some(days[1]), "<p>写到一半</p>");
+
+        plan_dao::delete_plan_day(&conn, days[1]).unwrap();
+
+        let drafts = get_drafts(&conn).unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].plan_day_id, None);
+        assert_eq!(drafts[0].content, "<p>自由写作</p>");
+    }
+}
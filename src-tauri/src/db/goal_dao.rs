@@ -0,0 +1,152 @@
+//! 写作目标数据库操作
+
+use rusqlite::{params, Connection};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use crate::errors::{AppError, AppResult};
+use crate::models::goal::*;
+
+const GOAL_COLUMNS: &str = "id, period, metric, target, created_at, updated_at";
+
+fn map_goal_row(row: &rusqlite::Row) -> rusqlite::Result<WritingGoal> {
+    Ok(WritingGoal {
+        id: row.get(0)?,
+        period: GoalPeriod::from_str(&row.get::<_, String>(1)?),
+        metric: GoalMetric::from_str(&row.get::<_, String>(2)?),
+        target: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+/// 获取所有目标（按周期、指标排序）
+pub fn get_goals(conn: &Connection) -> AppResult<Vec<WritingGoal>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM writing_goals
+         ORDER BY CASE period WHEN 'day' THEN 0 WHEN 'week' THEN 1 ELSE 2 END, metric",
+        GOAL_COLUMNS
+    ))?;
+    let goals = stmt.query_map([], map_goal_row)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(goals)
+}
+
+/// 根据 ID 获取目标
+pub fn get_goal_by_id(conn: &Connection, id: i64) -> AppResult<WritingGoal> {
+    conn.query_row(
+        &format!("SELECT {} FROM writing_goals WHERE id = ?1", GOAL_COLUMNS),
+        params![id],
+        map_goal_row,
+    ).map_err(|_| AppError::NotFound(format!("写作目标 ID {} 不存在", id)))
+}
+
+/// 设置目标（同周期同指标已存在时更新目标值）
+pub fn set_goal(conn: &Connection, req: &SetWritingGoalRequest) -> AppResult<WritingGoal> {
+    if req.target <= 0 {
+        return Err(AppError::Business("目标值需要大于 0".to_string()));
+    }
+
+    conn.execute(
+        "INSERT INTO writing_goals (period, metric, target) VALUES (?1, ?2, ?3)
+         ON CONFLICT(period, metric) DO UPDATE SET target = excluded.target, updated_at = datetime('now', 'localtime')",
+        params![req.period.as_str(), req.metric.as_str(), req.target],
+    )?;
+
+    let id: i64 = conn.query_row(
+        "SELECT id FROM writing_goals WHERE period = ?1 AND metric = ?2",
+        params![req.period.as_str(), req.metric.as_str()],
+        |row| row.get(0),
+    )?;
+    get_goal_by_id(conn, id)
+}
+
+/// 删除目标
+pub fn delete_goal(conn: &Connection, id: i64) -> AppResult<()> {
+    let affected = conn.execute("DELETE FROM writing_goals WHERE id = ?1", params![id])?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("写作目标 ID {} 不存在", id)));
+    }
+    Ok(())
+}
+
+/// 获取所有目标在 `date` 所在周期内的进度（`date` 为空时取今天）
+pub fn get_goal_progress(conn: &Connection, date: Option<&str>) -> AppResult<Vec<GoalProgress>> {
+    let date = match date {
+        Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)))?,
+        None => chrono::Local::now().date_naive(),
+    };
+
+    get_goals(conn)?.into_iter().map(|goal| {
+        let (start, end) = period_range(goal.period, date);
+        let (start, end) = (start.format("%Y-%m-%d").to_string(), end.format("%Y-%m-%d").to_string());
+        let current: i64 = conn.query_row(
+            &format!("SELECT {} FROM writings WHERE written_date BETWEEN ?1 AND ?2", metric_expression(goal.metric)),
+            params![start, end],
+            |row| row.get(0),
+        )?;
+
+        Ok(GoalProgress {
+            percent: current as f64 * 100.0 / goal.target as f64,
+            achieved: current >= goal.target,
+            current,
+            period_start: start,
+            period_end: end,
+            goal,
+        })
+    }).collect()
+}
+
+/// 每日目标均已达成的写作日期（降序）；没有每日目标时返回所有写过的日期
+pub fn get_daily_goal_met_dates(conn: &Connection) -> AppResult<Vec<String>> {
+    let daily_goals: Vec<WritingGoal> = get_goals(conn)?
+        .into_iter()
+        .filter(|g| g.period == GoalPeriod::Day)
+        .collect();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT written_date, {}, {}, {} FROM writings GROUP BY written_date ORDER BY written_date DESC",
+        metric_expression(GoalMetric::Words),
+        metric_expression(GoalMetric::Sessions),
+        metric_expression(GoalMetric::Duration),
+    ))?;
+    let days = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?))
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    let dates = days.into_iter()
+        .filter(|(_, words, sessions, duration)| daily_goals.iter().all(|g| {
+            let value = match g.metric {
+                GoalMetric::Words => *words,
+                GoalMetric::Sessions => *sessions,
+                GoalMetric::Duration => *duration,
+            };
+            value >= g.target
+        }))
+        .map(|(date, ..)| date)
+        .collect();
+    Ok(dates)
+}
+
+/// 指标对应的聚合表达式
+fn metric_expression(metric: GoalMetric) -> &'static str {
+    match metric {
+        GoalMetric::Words => "COALESCE(SUM(word_count), 0)",
+        GoalMetric::Sessions => "COUNT(*)",
+        GoalMetric::Duration => "COALESCE(SUM(duration_seconds), 0)",
+    }
+}
+
+/// `date` 所在周期的起止日期（均含）
+fn period_range(period: GoalPeriod, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match period {
+        GoalPeriod::Day => (date, date),
+        GoalPeriod::Week => {
+            let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            (start, start + Duration::days(6))
+        }
+        GoalPeriod::Month => {
+            let start = date.with_day(1).unwrap_or(date);
+            let end = start.checked_add_months(Months::new(1)).map(|d| d - Duration::days(1)).unwrap_or(date);
+            (start, end)
+        }
+    }
+}
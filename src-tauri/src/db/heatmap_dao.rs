@@ -0,0 +1,96 @@
+//! 热力图数据库操作
+
+use rusqlite::{params, Connection};
+use chrono::{Duration, NaiveDate};
+use crate::errors::{AppError, AppResult};
+use crate::models::heatmap::*;
+use crate::db::task_event_dao;
+
+/// 强度等级数上限
+const MAX_LEVELS: usize = 10;
+
+/// 获取热力图数据，强度等级按非零值的分位数划分
+pub fn get_heatmap(conn: &Connection, req: &HeatmapRequest) -> AppResult<Heatmap> {
+    if req.levels == 0 || req.levels > MAX_LEVELS {
+        return Err(AppError::Business(format!("强度等级数需要在 1 ~ {} 之间", MAX_LEVELS)));
+    }
+    let (start, end) = resolve_range(req)?;
+    let (start_str, end_str) = (start.format("%Y-%m-%d").to_string(), end.format("%Y-%m-%d").to_string());
+
+    let sql = match req.metric {
+        HeatmapMetric::Words =>
+            "SELECT written_date, COALESCE(SUM(word_count), 0) FROM writings
+             WHERE written_date BETWEEN ?1 AND ?2 GROUP BY written_date",
+        HeatmapMetric::Sessions =>
+            "SELECT written_date, COUNT(*) FROM writings
+             WHERE written_date BETWEEN ?1 AND ?2 GROUP BY written_date",
+        HeatmapMetric::Minutes =>
+            "SELECT written_date, CAST(ROUND(COALESCE(SUM(duration_seconds), 0) / 60.0) AS INTEGER) FROM writings
+             WHERE written_date BETWEEN ?1 AND ?2 GROUP BY written_date",
+        // 已被重新打开的完成不计入
+        HeatmapMetric::TasksCompleted => &format!(
+            "SELECT date(occurred_at), COUNT(*) FROM ({})
+             WHERE date(occurred_at) BETWEEN ?1 AND ?2 GROUP BY 1",
+            task_event_dao::EFFECTIVE_COMPLETIONS
+        ),
+    };
+
+    let mut stmt = conn.prepare(&format!("{} ORDER BY 1 ASC", sql))?;
+    let rows: Vec<(String, i64)> = stmt.query_map(params![start_str, end_str], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })?.collect::<Result<Vec<_>, _>>()?;
+    let rows: Vec<(String, i64)> = rows.into_iter().filter(|(_, v)| *v > 0).collect();
+
+    let thresholds = quantile_thresholds(rows.iter().map(|(_, v)| *v).collect(), req.levels);
+    let entries = rows.into_iter().map(|(date, value)| HeatmapEntry {
+        date,
+        value,
+        level: thresholds.iter().filter(|t| **t <= value).count().max(1),
+    }).collect::<Vec<_>>();
+
+    Ok(Heatmap {
+        start_date: start,
+        end_date: end,
+        metric: req.metric,
+        max_value: entries.iter().map(|e| e.value).max().unwrap_or(0),
+        total: entries.iter().map(|e| e.value).sum(),
+        thresholds,
+        entries,
+    })
+}
+
+/// 解析请求的日期范围
+fn resolve_range(req: &HeatmapRequest) -> AppResult<(NaiveDate, NaiveDate)> {
+    if let Some(year) = req.year {
+        let start = NaiveDate::from_ymd_opt(year, 1, 1);
+        let end = NaiveDate::from_ymd_opt(year, 12, 31);
+        return match (start, end) {
+            (Some(start), Some(end)) => Ok((start, end)),
+            _ => Err(AppError::Business(format!("年份无效: {}", year))),
+        };
+    }
+
+    let parse = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)));
+    let end = match req.end_date.as_deref() {
+        Some(s) => parse(s)?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let start = match req.start_date.as_deref() {
+        Some(s) => parse(s)?,
+        None => end - Duration::days(365),
+    };
+    if end < start {
+        return Err(AppError::Business("结束日期不能早于开始日期".to_string()));
+    }
+    Ok((start, end))
+}
+
+/// 按分位数计算各等级的下限：第 k 级（1 起）的下限为排序后第 (k-1)/levels 分位的值
+fn quantile_thresholds(mut values: Vec<i64>, levels: usize) -> Vec<i64> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+    values.sort_unstable();
+    (0..levels).map(|k| values[k * values.len() / levels]).collect()
+}
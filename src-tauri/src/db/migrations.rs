@@ -0,0 +1,674 @@
+//! 数据库版本化迁移
+//!
+//! 以 `PRAGMA user_version` 记录当前 schema 版本，启动时按顺序执行尚未应用的迁移。
+//! 每个迁移步骤在独立事务中执行，成功后才写入新的版本号；
+//! 对已有数据的数据库，迁移前会先备份一份 `writedo.db` 副本。
+//!
+//! 新增表或字段时，只需在 [`MIGRATIONS`] 末尾追加一个步骤，不要修改已发布的步骤。
+
+use rusqlite::{params, Connection, Transaction};
+use crate::errors::{AppError, AppResult};
+
+/// 单个迁移步骤
+pub struct Migration {
+    /// 迁移完成后的 schema 版本号（从 1 开始连续递增）
+    pub version: i64,
+    /// 迁移说明
+    pub description: &'static str,
+    /// 迁移逻辑
+    pub apply: fn(&Transaction) -> AppResult<()>,
+}
+
+/// 全部迁移步骤（按版本号升序）
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "初始表结构：tasks / writing_plans / plan_days / writings",
+        apply: v1_initial_schema,
+    },
+    Migration {
+        version: 2,
+        description: "重复任务：tasks.repeat_anchor + task_completions 完成记录",
+        apply: v2_task_recurrence,
+    },
+    Migration {
+        version: 3,
+        description: "RRULE 重复规则：tasks.rrule / tasks.exdates",
+        apply: v3_task_rrule,
+    },
+    Migration {
+        version: 4,
+        description: "截止时间与提醒：tasks.due_time + task_reminders",
+        apply: v4_task_reminders,
+    },
+    Migration {
+        version: 5,
+        description: "应用设置：app_settings 键值表",
+        apply: v5_app_settings,
+    },
+    Migration {
+        version: 6,
+        description: "任务清单：task_items + tasks.auto_complete",
+        apply: v6_task_items,
+    },
+    Migration {
+        version: 7,
+        description: "标签：tags / task_tags / writing_tags",
+        apply: v7_tags,
+    },
+    Migration {
+        version: 8,
+        description: "任务列表：task_lists + tasks.list_id",
+        apply: v8_task_lists,
+    },
+    Migration {
+        version: 9,
+        description: "全文搜索：writings / plan_days / tasks 的 FTS5 索引及同步触发器",
+        apply: v9_search_index,
+    },
+    Migration {
+        version: 10,
+        description: "写作记录：writings.updated_at",
+        apply: v10_writing_updated_at,
+    },
+    Migration {
+        version: 11,
+        description: "写作草稿：drafts",
+        apply: v11_drafts,
+    },
+    Migration {
+        version: 12,
+        description: "写作修订历史：writing_revisions",
+        apply: v12_writing_revisions,
+    },
+    Migration {
+        version: 13,
+        description: "写作字数明细：中日韩文字 / 拉丁词 / 标点 / 段落",
+        apply: v13_writing_metrics,
+    },
+    Migration {
+        version: 14,
+        description: "写作目标：writing_goals",
+        apply: v14_writing_goals,
+    },
+    Migration {
+        version: 15,
+        description: "任务完成历史：tasks.completed_at + task_events",
+        apply: v15_task_events,
+    },
+    Migration {
+        version: 16,
+        description: "计划补救方式：writing_plans.catch_up_mode",
+        apply: v16_plan_catch_up_mode,
+    },
+    Migration {
+        version: 17,
+        description: "计划暂停记录：writing_plans.paused_at / resumed_at / paused_seconds",
+        apply: v17_plan_pause_tracking,
+    },
+    Migration {
+        version: 18,
+        description: "计划完成时间与生命周期事件：writing_plans.completed_at + plan_events",
+        apply: v18_plan_events,
+    },
+    Migration {
+        version: 19,
+        description: "计划写作日：writing_plans.writing_weekdays / excluded_dates",
+        apply: v19_plan_writing_days,
+    },
+];
+
+/// 当前程序支持的最新 schema 版本
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// 读取数据库当前 schema 版本
+pub fn current_version(conn: &Connection) -> AppResult<i64> {
+    Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+}
+
+/// 执行所有尚未应用的迁移
+pub fn run_migrations(conn: &Connection) -> AppResult<()> {
+    let current = current_version(conn)?;
+    let latest = latest_version();
+
+    if current > latest {
+        return Err(AppError::DatabaseTooNew { found: current, supported: latest });
+    }
+    if current == latest {
+        return Ok(());
+    }
+
+    // 已有用户数据时，迁移前先备份
+    if current > 0 || has_user_tables(conn)? {
+        backup_database(conn, current)?;
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.unchecked_transaction()?;
+        (migration.apply)(&tx).map_err(|e| {
+            AppError::Migration(format!("v{}（{}）执行失败: {}", migration.version, migration.description, e))
+        })?;
+        // PRAGMA 不支持参数绑定，版本号为内部常量，直接拼接
+        tx.execute_batch(&format!("PRAGMA user_version = {};", migration.version))?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+/// 是否已存在业务表（用于识别早期未记录版本号的数据库）
+fn has_user_tables(conn: &Connection) -> AppResult<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'tasks'",
+        [], |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// 迁移前备份数据库
+///
+/// 使用 `VACUUM INTO` 生成一致的快照（包含尚未 checkpoint 的 WAL 内容），
+/// 备份文件与数据库位于同一目录：`writedo.db.v{版本}-{时间戳}.bak`。
+/// 内存数据库没有文件路径，直接跳过。
+fn backup_database(conn: &Connection, from_version: i64) -> AppResult<()> {
+    let db_path = match conn.path() {
+        Some(p) if !p.is_empty() => std::path::PathBuf::from(p),
+        _ => return Ok(()),
+    };
+
+    let stamp = chrono::Local::now().format("%Y%m%d%H%M%S");
+    let file_name = db_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| super::init::DB_NAME.to_string());
+    let backup_path = db_path.with_file_name(format!("{}.v{}-{}.bak", file_name, from_version, stamp));
+
+    conn.execute("VACUUM INTO ?1", params![backup_path.to_string_lossy()])
+        .map_err(|e| AppError::Migration(format!("迁移前备份失败: {}", e)))?;
+
+    Ok(())
+}
+
+// ==================== 迁移步骤 ====================
+
+/// v1：初始表结构
+///
+/// 使用 `IF NOT EXISTS`，兼容引入版本号之前创建的数据库。
+fn v1_initial_schema(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tasks (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            title           TEXT NOT NULL,
+            description     TEXT,
+            priority        TEXT NOT NULL DEFAULT 'medium',
+            due_date        TEXT,
+            repeat          TEXT,
+            completed       INTEGER NOT NULL DEFAULT 0,
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+        );
+
+        CREATE TABLE IF NOT EXISTS writing_plans (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            name            TEXT NOT NULL,
+            theme           TEXT,
+            start_date      TEXT NOT NULL,
+            total_days      INTEGER NOT NULL,
+            status          TEXT NOT NULL DEFAULT 'active',
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+        );
+
+        CREATE TABLE IF NOT EXISTS plan_days (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            plan_id         INTEGER NOT NULL,
+            day_number      INTEGER NOT NULL,
+            title           TEXT NOT NULL,
+            prompt          TEXT NOT NULL,
+            scheduled_date  TEXT,
+            FOREIGN KEY (plan_id) REFERENCES writing_plans(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS writings (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            plan_day_id     INTEGER,
+            title           TEXT NOT NULL,
+            content         TEXT NOT NULL DEFAULT '',
+            word_count      INTEGER NOT NULL DEFAULT 0,
+            duration_seconds INTEGER NOT NULL DEFAULT 0,
+            written_date    TEXT NOT NULL DEFAULT (date('now', 'localtime')),
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            FOREIGN KEY (plan_day_id) REFERENCES plan_days(id) ON DELETE SET NULL
+        );"
+    )?;
+    Ok(())
+}
+
+/// v2：重复任务的锚定日期与逐次完成记录
+///
+/// `repeat_anchor` 记录重复规则的起始日期，按月重复时用它保留原始日号
+/// （1/31 → 2/28 → 3/31，而不是一路变成 28 号）。
+fn v2_task_recurrence(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE tasks ADD COLUMN repeat_anchor TEXT;
+
+        UPDATE tasks SET repeat_anchor = due_date WHERE repeat IS NOT NULL;
+
+        CREATE TABLE IF NOT EXISTS task_completions (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id         INTEGER NOT NULL,
+            occurrence_date TEXT NOT NULL,
+            completed_at    TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_task_completions_task ON task_completions(task_id);"
+    )?;
+    Ok(())
+}
+
+/// v3：RFC 5545 重复规则
+///
+/// `exdates` 为逗号分隔的 `YYYY-MM-DD` 列表。
+fn v3_task_rrule(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE tasks ADD COLUMN rrule TEXT;
+        ALTER TABLE tasks ADD COLUMN exdates TEXT;"
+    )?;
+    Ok(())
+}
+
+/// v4：任务截止时间与提醒
+///
+/// `last_fired_at` 记录最近一次已发送的触发时刻，截止日期顺延后触发时刻变化，提醒会重新生效。
+fn v4_task_reminders(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE tasks ADD COLUMN due_time TEXT;
+
+        CREATE TABLE IF NOT EXISTS task_reminders (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id         INTEGER NOT NULL,
+            kind            TEXT NOT NULL DEFAULT 'before',
+            offset_minutes  INTEGER,
+            at_time         TEXT,
+            last_fired_at   TEXT,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_task_reminders_task ON task_reminders(task_id);"
+    )?;
+    Ok(())
+}
+
+/// v5：后端需要读取的应用设置（键值对，值为 JSON 或纯文本）
+fn v5_app_settings(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key             TEXT PRIMARY KEY,
+            value           TEXT NOT NULL,
+            updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+        );"
+    )?;
+    Ok(())
+}
+
+/// v6：任务清单项（子任务）
+fn v6_task_items(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE tasks ADD COLUMN auto_complete INTEGER NOT NULL DEFAULT 0;
+
+        CREATE TABLE IF NOT EXISTS task_items (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id         INTEGER NOT NULL,
+            title           TEXT NOT NULL,
+            completed       INTEGER NOT NULL DEFAULT 0,
+            sort_order      INTEGER NOT NULL DEFAULT 0,
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_task_items_task ON task_items(task_id, sort_order);"
+    )?;
+    Ok(())
+}
+
+/// v7：标签及任务 / 写作记录的关联表
+fn v7_tags(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tags (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            name            TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            color           TEXT,
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+        );
+
+        CREATE TABLE IF NOT EXISTS task_tags (
+            task_id         INTEGER NOT NULL,
+            tag_id          INTEGER NOT NULL,
+            PRIMARY KEY (task_id, tag_id),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS writing_tags (
+            writing_id      INTEGER NOT NULL,
+            tag_id          INTEGER NOT NULL,
+            PRIMARY KEY (writing_id, tag_id),
+            FOREIGN KEY (writing_id) REFERENCES writings(id) ON DELETE CASCADE,
+            FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_task_tags_tag ON task_tags(tag_id);
+        CREATE INDEX IF NOT EXISTS idx_writing_tags_tag ON writing_tags(tag_id);"
+    )?;
+    Ok(())
+}
+
+/// v8：任务列表（项目），任务通过 list_id 归属到列表
+fn v8_task_lists(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS task_lists (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            name            TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            color           TEXT,
+            sort_order      INTEGER NOT NULL DEFAULT 0,
+            archived        INTEGER NOT NULL DEFAULT 0,
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+        );
+
+        ALTER TABLE tasks ADD COLUMN list_id INTEGER REFERENCES task_lists(id) ON DELETE SET NULL;
+
+        CREATE INDEX IF NOT EXISTS idx_tasks_list ON tasks(list_id);"
+    )?;
+    Ok(())
+}
+
+/// v9：全文搜索索引
+///
+/// 每张表对应一个外部内容（external content）FTS5 表，由触发器保持同步。
+/// 使用 trigram 分词器，中文无需分词即可按子串匹配。
+/// 写作正文索引去掉 HTML 标签后的纯文本（`writings.plain_text`，由保存时写入），
+/// 避免搜索 `strong`、`span` 等词时命中标签。
+fn v9_search_index(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch("ALTER TABLE writings ADD COLUMN plain_text TEXT NOT NULL DEFAULT '';")?;
+
+    let rows: Vec<(i64, String)> = {
+        let mut stmt = tx.prepare("SELECT id, content FROM writings")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+    for (id, content) in rows {
+        tx.execute(
+            "UPDATE writings SET plain_text = ?1 WHERE id = ?2",
+            params![v9_html_to_text(&content), id],
+        )?;
+    }
+
+    tx.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS writings_fts USING fts5(
+            title, plain_text,
+            content = 'writings', content_rowid = 'id', tokenize = 'trigram'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS writings_fts_ai AFTER INSERT ON writings BEGIN
+            INSERT INTO writings_fts (rowid, title, plain_text) VALUES (new.id, new.title, new.plain_text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS writings_fts_ad AFTER DELETE ON writings BEGIN
+            INSERT INTO writings_fts (writings_fts, rowid, title, plain_text) VALUES ('delete', old.id, old.title, old.plain_text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS writings_fts_au AFTER UPDATE OF title, plain_text ON writings BEGIN
+            INSERT INTO writings_fts (writings_fts, rowid, title, plain_text) VALUES ('delete', old.id, old.title, old.plain_text);
+            INSERT INTO writings_fts (rowid, title, plain_text) VALUES (new.id, new.title, new.plain_text);
+        END;
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS plan_days_fts USING fts5(
+            title, prompt,
+            content = 'plan_days', content_rowid = 'id', tokenize = 'trigram'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS plan_days_fts_ai AFTER INSERT ON plan_days BEGIN
+            INSERT INTO plan_days_fts (rowid, title, prompt) VALUES (new.id, new.title, new.prompt);
+        END;
+        CREATE TRIGGER IF NOT EXISTS plan_days_fts_ad AFTER DELETE ON plan_days BEGIN
+            INSERT INTO plan_days_fts (plan_days_fts, rowid, title, prompt) VALUES ('delete', old.id, old.title, old.prompt);
+        END;
+        CREATE TRIGGER IF NOT EXISTS plan_days_fts_au AFTER UPDATE OF title, prompt ON plan_days BEGIN
+            INSERT INTO plan_days_fts (plan_days_fts, rowid, title, prompt) VALUES ('delete', old.id, old.title, old.prompt);
+            INSERT INTO plan_days_fts (rowid, title, prompt) VALUES (new.id, new.title, new.prompt);
+        END;
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS tasks_fts USING fts5(
+            title, description,
+            content = 'tasks', content_rowid = 'id', tokenize = 'trigram'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS tasks_fts_ai AFTER INSERT ON tasks BEGIN
+            INSERT INTO tasks_fts (rowid, title, description) VALUES (new.id, new.title, new.description);
+        END;
+        CREATE TRIGGER IF NOT EXISTS tasks_fts_ad AFTER DELETE ON tasks BEGIN
+            INSERT INTO tasks_fts (tasks_fts, rowid, title, description) VALUES ('delete', old.id, old.title, old.description);
+        END;
+        CREATE TRIGGER IF NOT EXISTS tasks_fts_au AFTER UPDATE OF title, description ON tasks BEGIN
+            INSERT INTO tasks_fts (tasks_fts, rowid, title, description) VALUES ('delete', old.id, old.title, old.description);
+            INSERT INTO tasks_fts (rowid, title, description) VALUES (new.id, new.title, new.description);
+        END;
+
+        INSERT INTO writings_fts (writings_fts) VALUES ('rebuild');
+        INSERT INTO plan_days_fts (plan_days_fts) VALUES ('rebuild');
+        INSERT INTO tasks_fts (tasks_fts) VALUES ('rebuild');"
+    )?;
+    Ok(())
+}
+
+/// v9 回填历史正文时使用的 HTML 转纯文本规则
+///
+/// 迁移结果不能随应用代码变化，这里固定一份迁移编写时的规则，
+/// 不调用 `text_metrics::html_to_text`。之后规则有调整时，由 recompute_writing_metrics 命令重新生成。
+fn v9_html_to_text(content: &str) -> String {
+    const BLOCK_END_TAGS: &[&str] = &["/p", "/h1", "/h2", "/h3", "/h4", "/h5", "/h6", "/li", "/blockquote", "/pre"];
+
+    let mut text = String::with_capacity(content.len());
+    let mut tag: Option<String> = None;
+    for c in content.chars() {
+        match (&mut tag, c) {
+            (None, '<') => tag = Some(String::new()),
+            (Some(name), '>') => {
+                let name = name.trim_end_matches('/').trim().to_lowercase();
+                let name = name.split_whitespace().next().unwrap_or("");
+                if name == "br" || BLOCK_END_TAGS.contains(&name) {
+                    text.push('\n');
+                }
+                tag = None;
+            }
+            (Some(name), _) => name.push(c),
+            (None, _) => text.push(c),
+        }
+    }
+
+    text.trim_end_matches('\n')
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// v10：写作记录的最后修改时间（历史记录取创建时间）
+fn v10_writing_updated_at(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE writings ADD COLUMN updated_at TEXT;
+        UPDATE writings SET updated_at = created_at;"
+    )?;
+    Ok(())
+}
+
+/// v11：写作草稿（自动保存），每个计划日条目或自由写作各占一个槽位
+///
+/// 槽位由 `plan_day_id` 推出，计划日条目（或整个计划）删除时其草稿随之删除，
+/// 不会留下槽位与条目对不上的草稿。
+fn v11_drafts(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS drafts (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            slot            TEXT NOT NULL UNIQUE,
+            plan_day_id     INTEGER,
+            title           TEXT NOT NULL DEFAULT '',
+            content         TEXT NOT NULL DEFAULT '',
+            word_count      INTEGER NOT NULL DEFAULT 0,
+            duration_seconds INTEGER NOT NULL DEFAULT 0,
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            FOREIGN KEY (plan_day_id) REFERENCES plan_days(id) ON DELETE CASCADE
+        );"
+    )?;
+    Ok(())
+}
+
+/// v12：写作记录修订历史（每次保存的完整快照），已有记录以当前内容作为第 1 版
+fn v12_writing_revisions(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS writing_revisions (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            writing_id      INTEGER NOT NULL,
+            revision_number INTEGER NOT NULL,
+            title           TEXT NOT NULL,
+            content         TEXT NOT NULL,
+            word_count      INTEGER NOT NULL DEFAULT 0,
+            created_at      TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            UNIQUE (writing_id, revision_number),
+            FOREIGN KEY (writing_id) REFERENCES writings(id) ON DELETE CASCADE
+        );
+
+        INSERT INTO writing_revisions (writing_id, revision_number, title, content, word_count, created_at)
+        SELECT id, 1, title, content, word_count, COALESCE(updated_at, created_at) FROM writings;"
+    )?;
+    Ok(())
+}
+
+/// v13：写作记录的字数统计明细（历史记录通过 recompute_writing_metrics 命令补算）
+fn v13_writing_metrics(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE writings ADD COLUMN cjk_chars INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE writings ADD COLUMN latin_words INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE writings ADD COLUMN punctuation_count INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE writings ADD COLUMN paragraph_count INTEGER NOT NULL DEFAULT 0;"
+    )?;
+    Ok(())
+}
+
+/// v14：写作目标（每日 / 每周 / 每月的字数、次数或时长目标）
+fn v14_writing_goals(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS writing_goals (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            period      TEXT NOT NULL CHECK (period IN ('day', 'week', 'month')),
+            metric      TEXT NOT NULL CHECK (metric IN ('words', 'sessions', 'duration')),
+            target      INTEGER NOT NULL CHECK (target > 0),
+            created_at  TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            UNIQUE (period, metric)
+        );"
+    )?;
+    Ok(())
+}
+
+/// v15：任务完成时间和事件日志
+///
+/// 已完成的任务以 updated_at 作为完成时间；事件从已有数据回填：
+/// 创建事件取 created_at，重复任务的完成取 task_completions，非重复任务的完成取 updated_at。
+fn v15_task_events(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE tasks ADD COLUMN completed_at TEXT;
+        UPDATE tasks SET completed_at = updated_at WHERE completed = 1;
+
+        CREATE TABLE IF NOT EXISTS task_events (
+            id                INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id           INTEGER NOT NULL,
+            event_type        TEXT NOT NULL CHECK (event_type IN ('created', 'completed', 'reopened', 'rescheduled')),
+            occurred_at       TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            due_date          TEXT,
+            previous_due_date TEXT,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_task_events_task ON task_events(task_id);
+        CREATE INDEX IF NOT EXISTS idx_task_events_type_time ON task_events(event_type, occurred_at);
+
+        INSERT INTO task_events (task_id, event_type, occurred_at, due_date)
+        SELECT task_id, event_type, occurred_at, due_date FROM (
+            SELECT id AS task_id, 'created' AS event_type, created_at AS occurred_at, due_date FROM tasks
+            UNION ALL
+            SELECT task_id, 'completed', completed_at, occurrence_date FROM task_completions
+            UNION ALL
+            SELECT id, 'completed', updated_at, due_date FROM tasks
+            WHERE completed = 1 AND repeat IS NULL AND rrule IS NULL
+        )
+        ORDER BY occurred_at ASC;"
+    )?;
+    Ok(())
+}
+
+/// v16：计划错过日期后的补救方式（shift / append / skip），已有计划保持原样（skip）
+fn v16_plan_catch_up_mode(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE writing_plans ADD COLUMN catch_up_mode TEXT NOT NULL DEFAULT 'skip';"
+    )?;
+    Ok(())
+}
+
+/// v17：计划暂停 / 恢复时间和累计暂停时长
+///
+/// 已处于暂停状态的计划无法得知暂停开始的时间，以迁移时刻作为本次暂停的开始。
+fn v17_plan_pause_tracking(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE writing_plans ADD COLUMN paused_at TEXT;
+        ALTER TABLE writing_plans ADD COLUMN resumed_at TEXT;
+        ALTER TABLE writing_plans ADD COLUMN paused_seconds INTEGER NOT NULL DEFAULT 0;
+
+        UPDATE writing_plans SET paused_at = datetime('now', 'localtime') WHERE status = 'paused';"
+    )?;
+    Ok(())
+}
+
+/// v18：计划完成时间和生命周期事件
+///
+/// 已有计划回填创建事件，正在暂停的计划回填暂停事件；
+/// 已完成计划的完成时间无从得知，保持为空。
+fn v18_plan_events(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE writing_plans ADD COLUMN completed_at TEXT;
+
+        CREATE TABLE IF NOT EXISTS plan_events (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            plan_id     INTEGER NOT NULL,
+            event_type  TEXT NOT NULL CHECK (event_type IN ('created', 'paused', 'resumed', 'completed', 'reopened')),
+            occurred_at TEXT NOT NULL DEFAULT (datetime('now', 'localtime')),
+            reason      TEXT,
+            FOREIGN KEY (plan_id) REFERENCES writing_plans(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_plan_events_plan ON plan_events(plan_id);
+
+        INSERT INTO plan_events (plan_id, event_type, occurred_at)
+        SELECT plan_id, event_type, occurred_at FROM (
+            SELECT id AS plan_id, 'created' AS event_type, created_at AS occurred_at FROM writing_plans
+            UNION ALL
+            SELECT id, 'paused', paused_at FROM writing_plans WHERE status = 'paused' AND paused_at IS NOT NULL
+        )
+        ORDER BY occurred_at ASC;"
+    )?;
+    Ok(())
+}
+
+/// v19：计划的每周写作日和排除日期（逗号分隔，为空表示每天都写、没有排除）
+fn v19_plan_writing_days(tx: &Transaction) -> AppResult<()> {
+    tx.execute_batch(
+        "ALTER TABLE writing_plans ADD COLUMN writing_weekdays TEXT;
+        ALTER TABLE writing_plans ADD COLUMN excluded_dates TEXT;"
+    )?;
+    Ok(())
+}
@@ -3,6 +3,19 @@
 //! 封装所有 SQLite 数据库操作，包括初始化、迁移和 CRUD。
 
 pub mod init;
+pub mod migrations;
 pub mod task_dao;
 pub mod plan_dao;
 pub mod writing_dao;
+pub mod settings_dao;
+pub mod name_rule;
+pub mod tag_dao;
+pub mod task_list_dao;
+pub mod search_dao;
+pub mod draft_dao;
+pub mod revision_dao;
+pub mod goal_dao;
+pub mod analytics_dao;
+pub mod heatmap_dao;
+pub mod task_event_dao;
+pub mod plan_event_dao;
@@ -0,0 +1,66 @@
+//! 名称唯一性校验
+//!
+//! 标签、任务列表等按名称区分的记录共用：名称去掉首尾空白后不能为空，
+//! 且在所在表中不区分大小写唯一。
+
+use rusqlite::{params, Connection};
+use crate::errors::{AppError, AppResult};
+
+/// 一类按名称区分的记录
+pub struct NameRule {
+    /// 所在的表（需有 `id` 和 `name` 列）
+    pub table: &'static str,
+    /// 错误提示中的名称，如"标签"
+    pub label: &'static str,
+}
+
+impl NameRule {
+    /// 校验名称，返回去掉首尾空白后的名称
+    pub fn validate(&self, name: &str) -> AppResult<String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::Business(format!("{}名称不能为空", self.label)));
+        }
+        Ok(name.to_string())
+    }
+
+    /// 确认没有其他记录（`except_id` 除外）使用同一名称
+    pub fn ensure_available(&self, conn: &Connection, name: &str, except_id: Option<i64>) -> AppResult<()> {
+        let count: i32 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE name = ?1 COLLATE NOCASE AND id != ?2", self.table),
+            params![name, except_id.unwrap_or(-1)],
+            |row| row.get(0),
+        )?;
+        if count > 0 {
+            return Err(AppError::Business(format!("{} \"{}\" 已存在", self.label, name)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::init_database;
+
+    const RULE: NameRule = NameRule { table: "tags", label: "标签" };
+
+    #[test]
+    fn trims_and_rejects_empty_names() {
+        assert_eq!(RULE.validate("  工作 ").unwrap(), "工作");
+        assert!(RULE.validate("   ").is_err());
+    }
+
+    #[test]
+    fn names_are_unique_ignoring_case() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        conn.execute("INSERT INTO tags (name) VALUES ('Work')", []).unwrap();
+        let id = conn.last_insert_rowid();
+
+        assert!(RULE.ensure_available(&conn, "work", None).is_err());
+        // 改名时可以保留自己的名称
+        assert!(RULE.ensure_available(&conn, "work", Some(id)).is_ok());
+        assert!(RULE.ensure_available(&conn, "Life", None).is_ok());
+    }
+}
@@ -1,54 +1,114 @@
 //! 写作计划数据库操作
 
-use rusqlite::{params, Connection};
-use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+use chrono::{NaiveDate, NaiveDateTime};
 use crate::errors::{AppError, AppResult};
 use crate::models::plan::*;
+use crate::models::plan_event::{PlanCompletedPayload, PlanCompletionReason, PlanEventType};
+use crate::models::settings::PlanCompletionPolicy;
+use crate::db::{plan_event_dao, settings_dao};
+use crate::plan_schedule::PlanSchedule;
 
-/// 创建写作计划（含每日条目）
+/// 创建写作计划（含每日条目），第 N 天安排在开始日期之后的第 N 个写作日
 pub fn create_plan(conn: &Connection, plan: &ImportPlanRequest) -> AppResult<i64> {
+    let start = NaiveDate::parse_from_str(&plan.start_date, "%Y-%m-%d")
+        .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)))?;
+    let schedule = PlanSchedule::new(&plan.writing_weekdays, &plan.excluded_dates)?;
+
     conn.execute(
-        "INSERT INTO writing_plans (name, theme, start_date, total_days) VALUES (?1, ?2, ?3, ?4)",
-        params![plan.name, plan.theme, plan.start_date, plan.days.len() as i32],
+        "INSERT INTO writing_plans (name, theme, start_date, total_days, writing_weekdays, excluded_dates)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            plan.name,
+            plan.theme,
+            plan.start_date,
+            plan.days.len() as i32,
+            join_weekdays(&schedule.weekdays()),
+            join_dates(&schedule.excluded_dates()),
+        ],
     )?;
 
     let plan_id = conn.last_insert_rowid();
-    let start = NaiveDate::parse_from_str(&plan.start_date, "%Y-%m-%d")
-        .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)))?;
 
     for day_item in &plan.days {
-        let scheduled = start + chrono::Duration::days((day_item.day - 1) as i64);
+        let scheduled = schedule.nth_date(start, (day_item.day - 1).max(0) as usize)?;
         conn.execute(
             "INSERT INTO plan_days (plan_id, day_number, title, prompt, scheduled_date) VALUES (?1, ?2, ?3, ?4, ?5)",
             params![plan_id, day_item.day, day_item.title, day_item.prompt, scheduled.to_string()],
         )?;
     }
 
+    let created_at: String = conn.query_row(
+        "SELECT created_at FROM writing_plans WHERE id = ?1",
+        params![plan_id],
+        |row| row.get(0),
+    )?;
+    plan_event_dao::record_event(conn, plan_id, PlanEventType::Created, &created_at, None)?;
+
     Ok(plan_id)
 }
 
+/// 计划查询的列（顺序与 [`map_plan_row`] 对应）
+///
+/// 暂停时长包含尚未恢复的本次暂停。
+const PLAN_COLUMNS: &str =
+    "wp.id, wp.name, wp.theme, wp.start_date, wp.total_days, wp.status, wp.created_at,
+     (SELECT COUNT(DISTINCT pd.id) FROM plan_days pd
+      INNER JOIN writings w ON w.plan_day_id = pd.id
+      WHERE pd.plan_id = wp.id) as completed_days,
+     wp.catch_up_mode, wp.paused_at, wp.resumed_at,
+     wp.paused_seconds + IFNULL(
+         CAST(strftime('%s', 'now', 'localtime') AS INTEGER) - CAST(strftime('%s', wp.paused_at) AS INTEGER), 0),
+     wp.completed_at, wp.writing_weekdays, wp.excluded_dates";
+
+fn map_plan_row(row: &rusqlite::Row) -> rusqlite::Result<WritingPlan> {
+    Ok(WritingPlan {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        theme: row.get(2)?,
+        start_date: row.get(3)?,
+        total_days: row.get(4)?,
+        status: PlanStatus::from_str(&row.get::<_, String>(5)?),
+        created_at: row.get(6)?,
+        completed_days: row.get(7)?,
+        catch_up_mode: CatchUpMode::from_str(&row.get::<_, String>(8)?),
+        paused_at: row.get(9)?,
+        resumed_at: row.get(10)?,
+        paused_seconds: row.get(11)?,
+        completed_at: row.get(12)?,
+        writing_weekdays: row.get::<_, Option<String>>(13)?
+            .map(|s| s.split(',').filter_map(|d| d.trim().parse().ok()).collect())
+            .unwrap_or_default(),
+        excluded_dates: row.get::<_, Option<String>>(14)?
+            .map(|s| s.split(',').filter(|d| !d.is_empty()).map(|d| d.to_string()).collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// 每周写作日存储为逗号分隔的数字，每天都写时为空
+fn join_weekdays(weekdays: &[u32]) -> Option<String> {
+    (!weekdays.is_empty()).then(|| weekdays.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(","))
+}
+
+/// 排除日期存储为逗号分隔的 YYYY-MM-DD，没有时为空
+fn join_dates(dates: &[String]) -> Option<String> {
+    (!dates.is_empty()).then(|| dates.join(","))
+}
+
+/// 计划的写作日规则
+fn plan_schedule(plan: &WritingPlan) -> AppResult<PlanSchedule> {
+    PlanSchedule::new(&plan.writing_weekdays, &plan.excluded_dates)
+}
+
 /// 获取所有写作计划（不含每日条目详情）
 pub fn get_all_plans(conn: &Connection) -> AppResult<Vec<WritingPlan>> {
-    let mut stmt = conn.prepare(
-        "SELECT wp.id, wp.name, wp.theme, wp.start_date, wp.total_days, wp.status, wp.created_at,
-                (SELECT COUNT(DISTINCT pd.id) FROM plan_days pd
-                 INNER JOIN writings w ON w.plan_day_id = pd.id
-                 WHERE pd.plan_id = wp.id) as completed_days
-         FROM writing_plans wp ORDER BY wp.created_at DESC"
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM writing_plans wp ORDER BY wp.created_at DESC",
+        PLAN_COLUMNS
+    ))?;
 
-    let plans = stmt.query_map([], |row| {
-        Ok(WritingPlan {
-            id: Some(row.get(0)?),
-            name: row.get(1)?,
-            theme: row.get(2)?,
-            start_date: row.get(3)?,
-            total_days: row.get(4)?,
-            status: PlanStatus::from_str(&row.get::<_, String>(5)?),
-            created_at: row.get(6)?,
-            completed_days: row.get(7)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+    let plans = stmt.query_map([], map_plan_row)?.collect::<Result<Vec<_>, _>>()?;
 
     Ok(plans)
 }
@@ -57,15 +117,18 @@ pub fn get_all_plans(conn: &Connection) -> AppResult<Vec<WritingPlan>> {
 pub fn get_plan_with_days(conn: &Connection, plan_id: i64) -> AppResult<PlanWithDays> {
     let plan = get_plan_by_id(conn, plan_id)?;
 
-    // LEFT JOIN writings 获取每日写作状态
+    // 按计划日汇总写作记录（同一天可能有多篇），展示最近一篇的标题
     let mut stmt = conn.prepare(
         "SELECT pd.id, pd.plan_id, pd.day_number, pd.title, pd.prompt, pd.scheduled_date,
-                CASE WHEN w.id IS NOT NULL THEN 1 ELSE 0 END as is_completed,
-                COALESCE(w.word_count, 0) as word_count,
+                CASE WHEN ws.writing_id IS NOT NULL THEN 1 ELSE 0 END as is_completed,
+                COALESCE(ws.word_count, 0) as word_count,
                 w.title as writing_title,
-                w.id as writing_id
+                ws.writing_id
          FROM plan_days pd
-         LEFT JOIN writings w ON w.plan_day_id = pd.id
+         LEFT JOIN (SELECT plan_day_id, SUM(word_count) as word_count, MAX(id) as writing_id
+                    FROM writings WHERE plan_day_id IS NOT NULL
+                    GROUP BY plan_day_id) ws ON ws.plan_day_id = pd.id
+         LEFT JOIN writings w ON w.id = ws.writing_id
          WHERE pd.plan_id = ?1
          ORDER BY pd.day_number ASC"
     )?;
@@ -95,26 +158,10 @@ pub fn get_plan_with_days(conn: &Connection, plan_id: i64) -> AppResult<PlanWith
 
 /// 根据 ID 获取计划
 fn get_plan_by_id(conn: &Connection, id: i64) -> AppResult<WritingPlan> {
-    let mut stmt = conn.prepare(
-        "SELECT wp.id, wp.name, wp.theme, wp.start_date, wp.total_days, wp.status, wp.created_at,
-                (SELECT COUNT(DISTINCT pd.id) FROM plan_days pd
-                 INNER JOIN writings w ON w.plan_day_id = pd.id
-                 WHERE pd.plan_id = wp.id) as completed_days
-         FROM writing_plans wp WHERE wp.id = ?1"
-    )?;
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM writing_plans wp WHERE wp.id = ?1", PLAN_COLUMNS))?;
 
-    stmt.query_row(params![id], |row| {
-        Ok(WritingPlan {
-            id: Some(row.get(0)?),
-            name: row.get(1)?,
-            theme: row.get(2)?,
-            start_date: row.get(3)?,
-            total_days: row.get(4)?,
-            status: PlanStatus::from_str(&row.get::<_, String>(5)?),
-            created_at: row.get(6)?,
-            completed_days: row.get(7)?,
-        })
-    }).map_err(|_| AppError::NotFound(format!("计划 ID {} 不存在", id)))
+    stmt.query_row(params![id], map_plan_row)
+        .map_err(|_| AppError::NotFound(format!("计划 ID {} 不存在", id)))
 }
 
 /// 获取今日写作任务
@@ -153,6 +200,7 @@ pub fn get_today_writing_task(conn: &Connection) -> AppResult<Option<TodayWritin
             Ok(Some(TodayWritingTask {
                 plan_name,
                 plan_id,
+                plan_day_id,
                 day_number,
                 title,
                 prompt,
@@ -164,8 +212,26 @@ pub fn get_today_writing_task(conn: &Connection) -> AppResult<Option<TodayWritin
     }
 }
 
-/// 更新计划基本信息
-pub fn update_plan(conn: &Connection, req: &UpdatePlanRequest) -> AppResult<WritingPlan> {
+/// 更新计划基本信息，返回更新后的计划详情
+///
+/// 请求显式开启重排（`reflow_days`）且开始日期或写作日规则确实发生变化时，
+/// 按新的规则从开始日期起重新排布所有未完成条目的日期；已完成的条目保持原日期
+/// 不动，未完成条目不会与其同日，也不会排到它之前。计划与条目的修改在同一事务中完成。
+pub fn update_plan(conn: &Connection, req: &UpdatePlanRequest) -> AppResult<PlanWithDays> {
+    let plan = get_plan_by_id(conn, req.id)?;
+    let start_date = req.start_date.as_deref().unwrap_or(&plan.start_date);
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
+        .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)))?;
+    let schedule = PlanSchedule::new(
+        req.writing_weekdays.as_deref().unwrap_or(&plan.writing_weekdays),
+        req.excluded_dates.as_deref().unwrap_or(&plan.excluded_dates),
+    )?;
+    let current = plan_schedule(&plan)?;
+    let changed = start.to_string() != plan.start_date
+        || schedule.weekdays() != current.weekdays()
+        || schedule.excluded_dates() != current.excluded_dates();
+    let reschedule = req.reflow_days.unwrap_or(false) && changed;
+
     let mut updates = Vec::new();
     let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
@@ -181,9 +247,21 @@ pub fn update_plan(conn: &Connection, req: &UpdatePlanRequest) -> AppResult<Writ
         updates.push("start_date = ?");
         values.push(Box::new(start_date.clone()));
     }
+    if let Some(mode) = req.catch_up_mode {
+        updates.push("catch_up_mode = ?");
+        values.push(Box::new(mode.as_str().to_string()));
+    }
+    if req.writing_weekdays.is_some() {
+        updates.push("writing_weekdays = ?");
+        values.push(Box::new(join_weekdays(&schedule.weekdays())));
+    }
+    if req.excluded_dates.is_some() {
+        updates.push("excluded_dates = ?");
+        values.push(Box::new(join_dates(&schedule.excluded_dates())));
+    }
 
     if updates.is_empty() {
-        return get_plan_by_id(conn, req.id);
+        return get_plan_with_days(conn, req.id);
     }
 
     values.push(Box::new(req.id));
@@ -192,26 +270,281 @@ pub fn update_plan(conn: &Connection, req: &UpdatePlanRequest) -> AppResult<Writ
         updates.join(", ")
     );
 
+    let tx = conn.unchecked_transaction()?;
     let params_refs: Vec<&dyn rusqlite::types::ToSql> = values.iter().map(|v| v.as_ref()).collect();
-    let affected = conn.execute(&sql, params_refs.as_slice())?;
-    if affected == 0 {
-        return Err(AppError::NotFound(format!("计划 ID {} 不存在", req.id)));
+    tx.execute(&sql, params_refs.as_slice())?;
+
+    if reschedule {
+        reflow_days(&tx, req.id, &schedule, start)?;
     }
-    get_plan_by_id(conn, req.id)
+    tx.commit()?;
+
+    get_plan_with_days(conn, req.id)
 }
 
-/// 更新计划状态
-pub fn update_plan_status(conn: &Connection, plan_id: i64, status: PlanStatus) -> AppResult<()> {
-    let affected = conn.execute(
+/// 从 `start` 起按写作日规则依次重排未完成条目的日期，已完成条目的日期保持不变
+fn reflow_days(conn: &Connection, plan_id: i64, schedule: &PlanSchedule, start: NaiveDate) -> AppResult<()> {
+    let mut stmt = conn.prepare(
+        "SELECT pd.id, pd.scheduled_date,
+                EXISTS (SELECT 1 FROM writings w WHERE w.plan_day_id = pd.id)
+         FROM plan_days pd WHERE pd.plan_id = ?1
+         ORDER BY pd.day_number ASC"
+    )?;
+    let days = stmt.query_map(params![plan_id], |row| Ok((
+        row.get::<_, i64>(0)?,
+        row.get::<_, Option<String>>(1)?
+            .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+        row.get::<_, bool>(2)?,
+    )))?.collect::<Result<Vec<_>, _>>()?;
+
+    let pinned: HashSet<NaiveDate> = days.iter()
+        .filter(|(_, _, completed)| *completed)
+        .filter_map(|(_, date, _)| *date)
+        .collect();
+
+    let mut cursor = start;
+    for (id, date, completed) in days {
+        if completed {
+            if let Some(date) = date {
+                cursor = cursor.max(date + chrono::Duration::days(1));
+            }
+            continue;
+        }
+        let target = schedule.next_free_on_or_after(cursor, &pinned)?;
+        conn.execute(
+            "UPDATE plan_days SET scheduled_date = ?1 WHERE id = ?2",
+            params![target.to_string(), id],
+        )?;
+        cursor = target + chrono::Duration::days(1);
+    }
+    Ok(())
+}
+
+/// 更新计划状态（手动），并记录生命周期事件
+///
+/// 暂停时记录暂停时刻；离开暂停状态时累计暂停时长，恢复为进行中时
+/// 把所有未完成条目的日期按暂停的天数顺延，使计划从暂停处继续。
+/// 已完成的计划改为进行中或暂停时，记录重新开始事件并清除完成时间。
+pub fn update_plan_status(conn: &Connection, plan_id: i64, status: PlanStatus, now: NaiveDateTime) -> AppResult<()> {
+    let plan = get_plan_by_id(conn, plan_id)?;
+    let now_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
         "UPDATE writing_plans SET status = ?1 WHERE id = ?2",
         params![status.as_str(), plan_id],
     )?;
-    if affected == 0 {
-        return Err(AppError::NotFound(format!("计划 ID {} 不存在", plan_id)));
+
+    if status == PlanStatus::Paused && plan.status != PlanStatus::Paused {
+        tx.execute(
+            "UPDATE writing_plans SET paused_at = ?1 WHERE id = ?2",
+            params![now_str, plan_id],
+        )?;
+    } else if status != PlanStatus::Paused && plan.status == PlanStatus::Paused {
+        let paused_at = plan.paused_at.as_deref()
+            .and_then(|s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok());
+        let paused_seconds = paused_at.map(|p| (now - p).num_seconds().max(0)).unwrap_or(0);
+        tx.execute(
+            "UPDATE writing_plans SET paused_at = NULL, resumed_at = ?1, paused_seconds = paused_seconds + ?2
+             WHERE id = ?3",
+            params![now_str, paused_seconds, plan_id],
+        )?;
+
+        let paused_days = paused_at.map(|p| (now.date() - p.date()).num_days()).unwrap_or(0);
+        if status == PlanStatus::Active && paused_days > 0 {
+            let open_days = get_open_days(&tx, plan_id)?;
+            let dates: Vec<NaiveDate> = open_days.iter().map(|(_, d)| *d).collect();
+            for ((id, _), date) in open_days.iter().zip(plan_schedule(&plan)?.shift(&dates, paused_days, &HashSet::new())?) {
+                tx.execute(
+                    "UPDATE plan_days SET scheduled_date = ?1 WHERE id = ?2",
+                    params![date.to_string(), id],
+                )?;
+            }
+        }
+    }
+
+    // 离开已完成状态（重新开始或直接暂停）都视为重新开始，清除完成时间
+    if plan.status == PlanStatus::Completed && status != PlanStatus::Completed {
+        tx.execute("UPDATE writing_plans SET completed_at = NULL WHERE id = ?1", params![plan_id])?;
+        plan_event_dao::record_event(&tx, plan_id, PlanEventType::Reopened, &now_str, None)?;
+    }
+    match (&plan.status, &status) {
+        (old, new) if old == new => {}
+        (_, PlanStatus::Paused) => {
+            plan_event_dao::record_event(&tx, plan_id, PlanEventType::Paused, &now_str, None)?;
+        }
+        (_, PlanStatus::Completed) => {
+            mark_completed(&tx, plan_id, &now_str, PlanCompletionReason::Manual)?;
+        }
+        (PlanStatus::Completed, PlanStatus::Active) => {}
+        (_, PlanStatus::Active) => {
+            plan_event_dao::record_event(&tx, plan_id, PlanEventType::Resumed, &now_str, None)?;
+        }
     }
+
+    tx.commit()?;
     Ok(())
 }
 
+/// 计划中有日期、尚未写作的条目 (id, scheduled_date)，按序号排列
+fn get_open_days(conn: &Connection, plan_id: i64) -> AppResult<Vec<(i64, NaiveDate)>> {
+    let mut stmt = conn.prepare(
+        "SELECT pd.id, pd.scheduled_date FROM plan_days pd
+         WHERE pd.plan_id = ?1 AND pd.scheduled_date IS NOT NULL
+           AND NOT EXISTS (SELECT 1 FROM writings w WHERE w.plan_day_id = pd.id)
+         ORDER BY pd.day_number ASC"
+    )?;
+    let days = stmt.query_map(params![plan_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(days.into_iter()
+        .filter_map(|(id, date)| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok().map(|d| (id, d)))
+        .collect())
+}
+
+/// 写作记录删除或取消关联计划日后，重新检查该计划日所属计划的完成状态
+///
+/// 只处理因"每天都已写作"而自动完成的计划：不再满足条件时恢复为进行中（记录重新开始事件），
+/// 返回是否已恢复。手动完成、因最后计划日已过而完成的计划保持已完成。
+pub fn recheck_completion(conn: &Connection, plan_day_id: i64, now: NaiveDateTime) -> AppResult<bool> {
+    let plan_id: Option<i64> = conn.query_row(
+        "SELECT plan_id FROM plan_days WHERE id = ?1",
+        params![plan_day_id],
+        |row| row.get(0),
+    ).optional()?;
+    let Some(plan_id) = plan_id else { return Ok(false) };
+    if get_plan_by_id(conn, plan_id)?.status != PlanStatus::Completed {
+        return Ok(false);
+    }
+
+    let reason: Option<String> = conn.query_row(
+        "SELECT reason FROM plan_events WHERE plan_id = ?1 AND event_type = ?2
+         ORDER BY id DESC LIMIT 1",
+        params![plan_id, PlanEventType::Completed.as_str()],
+        |row| row.get(0),
+    ).optional()?.flatten();
+    if reason.as_deref() != Some(PlanCompletionReason::AllDaysWritten.as_str()) {
+        return Ok(false);
+    }
+
+    let all_written: bool = conn.query_row(
+        "SELECT NOT EXISTS (
+             SELECT 1 FROM plan_days pd WHERE pd.plan_id = ?1
+               AND NOT EXISTS (SELECT 1 FROM writings w WHERE w.plan_day_id = pd.id)
+         )",
+        params![plan_id],
+        |row| row.get(0),
+    )?;
+    if all_written {
+        return Ok(false);
+    }
+
+    update_plan_status(conn, plan_id, PlanStatus::Active, now)?;
+    Ok(true)
+}
+
+/// 按自动完成设置检查进行中的计划，把满足条件的计划标记为已完成，返回本次完成的计划
+///
+/// 手动重新开始的计划，只有在重新开始之后再次满足条件（又写了新的条目，
+/// 或新的最后计划日也过了）才会再次自动完成。暂停中的计划不会自动完成。
+pub fn auto_complete_plans(conn: &Connection, now: NaiveDateTime) -> AppResult<Vec<PlanCompletedPayload>> {
+    let policy = settings_dao::get_plan_completion_settings(conn)?.policy;
+    if policy == PlanCompletionPolicy::Manual {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT wp.id, wp.name,
+                COUNT(DISTINCT pd.id) AS day_count,
+                COUNT(DISTINCT w.plan_day_id) AS written_count,
+                MAX(pd.scheduled_date) AS end_date,
+                MAX(COALESCE(w.updated_at, w.created_at)) AS last_written_at,
+                (SELECT MAX(e.occurred_at) FROM plan_events e
+                 WHERE e.plan_id = wp.id AND e.event_type = 'reopened') AS reopened_at
+         FROM writing_plans wp
+         LEFT JOIN plan_days pd ON pd.plan_id = wp.id
+         LEFT JOIN writings w ON w.plan_day_id = pd.id
+         WHERE wp.status = 'active'
+         GROUP BY wp.id"
+    )?;
+    let plans = stmt.query_map([], |row| {
+        Ok(CompletionCandidate {
+            plan_id: row.get(0)?,
+            plan_name: row.get(1)?,
+            day_count: row.get(2)?,
+            written_count: row.get(3)?,
+            end_date: row.get(4)?,
+            last_written_at: row.get(5)?,
+            reopened_at: row.get(6)?,
+        })
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    let today = now.date().format("%Y-%m-%d").to_string();
+    let now_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut completed = Vec::new();
+
+    for CompletionCandidate { plan_id, plan_name, day_count, written_count, end_date, last_written_at, reopened_at } in plans {
+        let all_written = day_count > 0
+            && written_count == day_count
+            && reopened_at.as_ref().is_none_or(|r| last_written_at.as_ref().is_some_and(|w| w > r));
+        let end_passed = end_date.as_ref().is_some_and(|end| {
+            *end < today && reopened_at.as_ref().is_none_or(|r| r.get(..10).is_some_and(|d| d <= end.as_str()))
+        });
+
+        let reason = match policy {
+            PlanCompletionPolicy::Manual => None,
+            PlanCompletionPolicy::AllDaysWritten => all_written.then_some(PlanCompletionReason::AllDaysWritten),
+            PlanCompletionPolicy::EndDatePassed => end_passed.then_some(PlanCompletionReason::EndDatePassed),
+            PlanCompletionPolicy::Either => {
+                if all_written {
+                    Some(PlanCompletionReason::AllDaysWritten)
+                } else {
+                    end_passed.then_some(PlanCompletionReason::EndDatePassed)
+                }
+            }
+        };
+        let Some(reason) = reason else { continue };
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE writing_plans SET status = ?1 WHERE id = ?2",
+            params![PlanStatus::Completed.as_str(), plan_id],
+        )?;
+        mark_completed(&tx, plan_id, &now_str, reason)?;
+        tx.commit()?;
+
+        completed.push(PlanCompletedPayload {
+            plan_id,
+            plan_name,
+            completed_at: now_str.clone(),
+            reason,
+            completed_days: written_count,
+            total_days: day_count,
+        });
+    }
+
+    Ok(completed)
+}
+
+/// 自动完成检查中一个进行中计划的汇总
+struct CompletionCandidate {
+    plan_id: i64,
+    plan_name: String,
+    day_count: i32,
+    written_count: i32,
+    /// 最后一个计划日
+    end_date: Option<String>,
+    /// 最近一次写作（或改动关联）的时间
+    last_written_at: Option<String>,
+    /// 最近一次手动重新开始的时间
+    reopened_at: Option<String>,
+}
+
+/// 记录计划的完成时间和完成事件（状态由调用方更新）
+fn mark_completed(conn: &Connection, plan_id: i64, now: &str, reason: PlanCompletionReason) -> AppResult<()> {
+    conn.execute("UPDATE writing_plans SET completed_at = ?1 WHERE id = ?2", params![now, plan_id])?;
+    plan_event_dao::record_event(conn, plan_id, PlanEventType::Completed, now, Some(reason))
+}
+
 /// 删除计划（级联删除每日条目）
 pub fn delete_plan(conn: &Connection, plan_id: i64) -> AppResult<()> {
     let affected = conn.execute("DELETE FROM writing_plans WHERE id = ?1", params![plan_id])?;
@@ -289,48 +622,372 @@ pub fn delete_plan_day(conn: &Connection, day_id: i64) -> AppResult<()> {
 }
 
 /// 在指定位置插入新条目（后续条目自动后移）
+///
+/// 未指定日期时，新条目使用被挤开的条目原来的日期；之后未完成的条目依次顺延到下一个写作日，
+/// 已完成条目的日期保持不动。
 pub fn add_plan_day(conn: &Connection, req: &AddPlanDayRequest) -> AppResult<()> {
     let plan_id = req.plan_id;
     let insert_at = req.day_number; // 要插入的位置
 
-    // 获取计划的 start_date 来计算 scheduled_date
-    let start_date: String = conn.query_row(
-        "SELECT start_date FROM writing_plans WHERE id = ?1",
-        params![plan_id],
-        |row| row.get(0),
-    ).map_err(|_| AppError::NotFound(format!("计划 ID {} 不存在", plan_id)))?;
+    // 获取计划的 start_date 和写作日规则来计算 scheduled_date
+    let plan = get_plan_by_id(conn, plan_id)?;
+    let schedule = plan_schedule(&plan)?;
+
+    // (id, day_number, scheduled_date, 是否已完成)，按序号排列
+    let mut stmt = conn.prepare(
+        "SELECT pd.id, pd.day_number, pd.scheduled_date,
+                EXISTS (SELECT 1 FROM writings w WHERE w.plan_day_id = pd.id)
+         FROM plan_days pd WHERE pd.plan_id = ?1
+         ORDER BY pd.day_number ASC"
+    )?;
+    let days: Vec<(i64, i32, Option<NaiveDate>, bool)> = stmt.query_map(params![plan_id], |row| {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get::<_, Option<String>>(2)?.and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
+            row.get(3)?,
+        ))
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    // 已完成条目的日期保持不动
+    let mut taken: HashSet<NaiveDate> = days.iter()
+        .filter(|(_, _, _, completed)| *completed)
+        .filter_map(|(_, _, date, _)| *date)
+        .collect();
+
+    // 使用自定义日期，或自动取被挤开的条目原来的日期（插在末尾时取最后一天之后的写作日）
+    let scheduled = match req.scheduled_date.clone() {
+        Some(date) => Some(date),
+        None => {
+            let base = match days.iter().find(|d| d.1 >= insert_at) {
+                Some((_, _, date, _)) => *date,
+                None => match days.iter().filter_map(|d| d.2).max() {
+                    Some(last) => Some(last + chrono::Duration::days(1)),
+                    None => NaiveDate::parse_from_str(&plan.start_date, "%Y-%m-%d").ok(),
+                },
+            };
+            base.map(|date| schedule.next_free_on_or_after(date, &taken)).transpose()?.map(|d| d.to_string())
+        }
+    };
+    let new_date = scheduled.as_deref().and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+
+    // 之后未完成的条目依次顺延，不与新条目及已完成条目挤在同一天
+    let mut shifted: Vec<(i64, NaiveDate)> = Vec::new();
+    if let Some(new_date) = new_date {
+        taken.insert(new_date);
+        let mut cursor = if req.scheduled_date.is_some() { NaiveDate::MIN } else { new_date };
+        for (id, _, date, _) in days.iter().filter(|d| d.1 >= insert_at && !d.3) {
+            let Some(date) = date else { continue };
+            let target = schedule.next_free_on_or_after((*date).max(cursor), &taken)?;
+            if target != *date {
+                shifted.push((*id, target));
+            }
+            cursor = target + chrono::Duration::days(1);
+        }
+    }
+
+    let tx = conn.unchecked_transaction()?;
 
     // 把 >= insert_at 的条目 day_number 全部 +1
-    conn.execute(
+    tx.execute(
         "UPDATE plan_days SET day_number = day_number + 1 WHERE plan_id = ?1 AND day_number >= ?2",
         params![plan_id, insert_at],
     )?;
-
-    // 使用自定义日期或自动计算
-    let scheduled = req.scheduled_date.clone().unwrap_or_else(|| {
-        NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
-            .map(|d| d + chrono::Duration::days((insert_at - 1) as i64))
-            .map(|d| d.to_string())
-            .unwrap_or_default()
-    });
+    for (id, date) in &shifted {
+        tx.execute(
+            "UPDATE plan_days SET scheduled_date = ?1 WHERE id = ?2",
+            params![date.to_string(), id],
+        )?;
+    }
 
     // 插入新条目
-    conn.execute(
+    tx.execute(
         "INSERT INTO plan_days (plan_id, day_number, title, prompt, scheduled_date) VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![plan_id, insert_at, req.title, req.prompt, scheduled],
+        params![plan_id, insert_at, req.title, req.prompt, scheduled.unwrap_or_default()],
     )?;
 
     // 更新 total_days
-    let count: i32 = conn.query_row(
+    let count: i32 = tx.query_row(
         "SELECT COUNT(*) FROM plan_days WHERE plan_id = ?1",
         params![plan_id],
         |row| row.get(0),
     )?;
-    conn.execute(
+    tx.execute(
         "UPDATE writing_plans SET total_days = ?1 WHERE id = ?2",
         params![count, plan_id],
     )?;
 
+    tx.commit()?;
     Ok(())
 }
 
+/// 补救错过的计划日：今天之前仍未完成的条目按补救方式重新安排日期
+///
+/// - `shift`：所有未完成条目整体顺延，使最早错过的条目落在今天，条目之间的间隔不变
+/// - `append`：错过的条目按原顺序移到计划末尾（序号随之调整），其余条目不变
+/// - `skip`：不做改动
+pub fn catch_up_plan(conn: &Connection, req: &CatchUpPlanRequest, today: NaiveDate) -> AppResult<CatchUpResult> {
+    let plan = get_plan_by_id(conn, req.plan_id)?;
+    if plan.status == PlanStatus::Completed {
+        return Err(AppError::Business("计划已完成，无需补救".to_string()));
+    }
+    let mode = req.mode.unwrap_or(plan.catch_up_mode);
+    let schedule = plan_schedule(&plan)?;
+
+    // (id, day_number, scheduled_date, 是否已完成)
+    let mut stmt = conn.prepare(
+        "SELECT pd.id, pd.day_number, pd.scheduled_date,
+                EXISTS (SELECT 1 FROM writings w WHERE w.plan_day_id = pd.id)
+         FROM plan_days pd WHERE pd.plan_id = ?1
+         ORDER BY pd.day_number ASC"
+    )?;
+    let days: Vec<(i64, i32, Option<NaiveDate>, bool)> = stmt.query_map(params![req.plan_id], |row| {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get::<_, Option<String>>(2)?.and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
+            row.get(3)?,
+        ))
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    let is_missed = |(_, _, date, completed): &(i64, i32, Option<NaiveDate>, bool)| {
+        !completed && date.is_some_and(|d| d < today)
+    };
+    let missed_days = days.iter().filter(|d| is_missed(d)).count() as i32;
+
+    // (id, 新的 scheduled_date, 新的 day_number)，为空表示不变
+    let mut changes: Vec<(i64, Option<NaiveDate>, Option<i32>)> = Vec::new();
+    match mode {
+        _ if missed_days == 0 => {}
+        CatchUpMode::Skip => {}
+        CatchUpMode::Shift => {
+            let earliest = days.iter().filter(|d| is_missed(d)).filter_map(|d| d.2).min().unwrap_or(today);
+            let delay = (today - earliest).num_days();
+            let open: Vec<(i64, NaiveDate)> = days.iter()
+                .filter_map(|(id, _, date, completed)| date.filter(|_| !completed).map(|d| (*id, d)))
+                .collect();
+            // 已完成条目的日期保持不动，顺延的条目不会与之挤在同一天（例如提前写完的后续条目）
+            let pinned: HashSet<NaiveDate> = days.iter()
+                .filter(|(_, _, _, completed)| *completed)
+                .filter_map(|(_, _, date, _)| *date)
+                .collect();
+            let dates: Vec<NaiveDate> = open.iter().map(|(_, d)| *d).collect();
+            for ((id, _), date) in open.iter().zip(schedule.shift(&dates, delay, &pinned)?) {
+                changes.push((*id, Some(date), None));
+            }
+        }
+        CatchUpMode::Append => {
+            let last = days.iter().filter_map(|d| d.2).max().unwrap_or(today);
+            let mut next = last.max(today - chrono::Duration::days(1));
+            let (missed, kept): (Vec<_>, Vec<_>) = days.iter().partition(|d| is_missed(d));
+            for (i, (id, day_number, _, _)) in kept.iter().enumerate() {
+                let number = i as i32 + 1;
+                if number != *day_number {
+                    changes.push((*id, None, Some(number)));
+                }
+            }
+            for (i, (id, _, _, _)) in missed.iter().enumerate() {
+                next = schedule.next_on_or_after(next + chrono::Duration::days(1))?;
+                changes.push((*id, Some(next), Some((kept.len() + i) as i32 + 1)));
+            }
+        }
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut rescheduled_days = 0;
+    for (id, date, day_number) in &changes {
+        let original = days.iter().find(|d| d.0 == *id).and_then(|d| d.2);
+        if let Some(date) = date.filter(|d| original != Some(*d)) {
+            rescheduled_days += 1;
+            tx.execute("UPDATE plan_days SET scheduled_date = ?1 WHERE id = ?2", params![date.to_string(), id])?;
+        }
+        if let Some(day_number) = day_number {
+            tx.execute("UPDATE plan_days SET day_number = ?1 WHERE id = ?2", params![day_number, id])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(CatchUpResult {
+        mode,
+        missed_days,
+        rescheduled_days,
+        plan: get_plan_with_days(conn, req.plan_id)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::init_database;
+    use crate::models::settings::PlanCompletionSettings;
+
+    fn dt(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// 创建一个两天的计划，返回计划 ID 和各计划日 ID
+    fn setup() -> (Connection, i64, Vec<i64>) {
+        setup_days(2)
+    }
+
+    /// 创建一个从 2026-10-01 开始、每天写作的 `n` 天计划
+    fn setup_days(n: i32) -> (Connection, i64, Vec<i64>) {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        settings_dao::save_plan_completion_settings(&conn, &PlanCompletionSettings {
+            policy: PlanCompletionPolicy::AllDaysWritten,
+        }).unwrap();
+        let days: Vec<_> = (1..=n)
+            .map(|day| serde_json::json!({"day": day, "title": format!("第{}天", day), "prompt": ""}))
+            .collect();
+        let req: ImportPlanRequest = serde_json::from_value(serde_json::json!({
+            "name": "计划", "start_date": "2026-10-01", "days": days,
+        })).unwrap();
+        let plan_id = create_plan(&conn, &req).unwrap();
+        let days = get_plan_with_days(&conn, plan_id).unwrap().days.iter().map(|d| d.id).collect();
+        (conn, plan_id, days)
+    }
+
+    fn write(conn: &Connection, plan_day_id: i64, at: &str) -> i64 {
+        conn.execute(
+            "INSERT INTO writings (plan_day_id, title, written_date, created_at, updated_at)
+             VALUES (?1, '写作', date(?2), ?2, ?2)",
+            params![plan_day_id, at],
+        ).unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn deleting_writing_reopens_plan_completed_by_all_days_written() {
+        let (conn, plan_id, days) = setup();
+        write(&conn, days[0], "2026-10-01 10:00:00");
+        let last = write(&conn, days[1], "2026-10-02 10:00:00");
+        assert_eq!(auto_complete_plans(&conn, dt("2026-10-02 10:00:01")).unwrap().len(), 1);
+
+        conn.execute("DELETE FROM writings WHERE id = ?1", params![last]).unwrap();
+        assert!(recheck_completion(&conn, days[1], dt("2026-10-02 11:00:00")).unwrap());
+
+        let plan = get_plan_by_id(&conn, plan_id).unwrap();
+        assert_eq!(plan.status, PlanStatus::Active);
+        assert!(plan.completed_at.is_none());
+        let events = plan_event_dao::get_plan_events(&conn, plan_id).unwrap();
+        assert!(events.iter().any(|e| e.event_type == PlanEventType::Reopened));
+
+        // 补写之后再次自动完成
+        write(&conn, days[1], "2026-10-02 12:00:00");
+        assert_eq!(auto_complete_plans(&conn, dt("2026-10-02 12:00:01")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn recheck_keeps_plan_completed_while_every_day_is_still_written() {
+        let (conn, plan_id, days) = setup();
+        write(&conn, days[0], "2026-10-01 10:00:00");
+        write(&conn, days[1], "2026-10-02 10:00:00");
+        let extra = write(&conn, days[1], "2026-10-02 10:30:00");
+        assert_eq!(auto_complete_plans(&conn, dt("2026-10-02 10:30:01")).unwrap().len(), 1);
+
+        conn.execute("DELETE FROM writings WHERE id = ?1", params![extra]).unwrap();
+        assert!(!recheck_completion(&conn, days[1], dt("2026-10-02 11:00:00")).unwrap());
+        assert_eq!(get_plan_by_id(&conn, plan_id).unwrap().status, PlanStatus::Completed);
+    }
+
+    #[test]
+    fn manually_completed_plan_stays_completed() {
+        let (conn, plan_id, days) = setup();
+        let writing = write(&conn, days[0], "2026-10-01 10:00:00");
+        update_plan_status(&conn, plan_id, PlanStatus::Completed, dt("2026-10-01 11:00:00")).unwrap();
+
+        conn.execute("DELETE FROM writings WHERE id = ?1", params![writing]).unwrap();
+        assert!(!recheck_completion(&conn, days[0], dt("2026-10-01 12:00:00")).unwrap());
+        let plan = get_plan_by_id(&conn, plan_id).unwrap();
+        assert_eq!(plan.status, PlanStatus::Completed);
+        assert!(plan.completed_at.is_some());
+    }
+
+    /// 各计划日 (序号, 日期)，按序号排列
+    fn schedule_of(plan: &PlanWithDays) -> Vec<(i32, &str)> {
+        plan.days.iter().map(|d| (d.day_number, d.scheduled_date.as_deref().unwrap_or(""))).collect()
+    }
+
+    #[test]
+    fn shift_does_not_collide_with_days_written_ahead() {
+        let (conn, plan_id, days) = setup_days(5);
+        write(&conn, days[0], "2026-10-01 10:00:00");
+        // 第 4 天（10-04）提前写完
+        write(&conn, days[3], "2026-10-02 10:00:00");
+
+        let req = CatchUpPlanRequest { plan_id, mode: Some(CatchUpMode::Shift) };
+        let result = catch_up_plan(&conn, &req, d("2026-10-03")).unwrap();
+        assert_eq!(result.missed_days, 1);
+        assert_eq!(result.rescheduled_days, 3);
+        assert_eq!(schedule_of(&result.plan), vec![
+            (1, "2026-10-01"), (2, "2026-10-03"), (3, "2026-10-05"), (4, "2026-10-04"), (5, "2026-10-06"),
+        ]);
+    }
+
+    #[test]
+    fn append_moves_missed_days_to_the_end() {
+        let (conn, plan_id, days) = setup_days(4);
+        write(&conn, days[0], "2026-10-01 10:00:00");
+
+        let req = CatchUpPlanRequest { plan_id, mode: Some(CatchUpMode::Append) };
+        let result = catch_up_plan(&conn, &req, d("2026-10-03")).unwrap();
+        assert_eq!(result.missed_days, 1);
+        assert_eq!(result.rescheduled_days, 1);
+        assert_eq!(schedule_of(&result.plan), vec![
+            (1, "2026-10-01"), (2, "2026-10-03"), (3, "2026-10-04"), (4, "2026-10-05"),
+        ]);
+        assert_eq!(result.plan.days[3].id, days[1]);
+
+        // 补救后不再有错过的条目
+        let again = catch_up_plan(&conn, &CatchUpPlanRequest { plan_id, mode: None }, d("2026-10-03")).unwrap();
+        assert_eq!(again.missed_days, 0);
+    }
+
+    #[test]
+    fn pausing_completed_plan_clears_completion() {
+        let (conn, plan_id, _) = setup();
+        update_plan_status(&conn, plan_id, PlanStatus::Completed, dt("2026-10-01 10:00:00")).unwrap();
+        update_plan_status(&conn, plan_id, PlanStatus::Paused, dt("2026-10-02 10:00:00")).unwrap();
+
+        let plan = get_plan_by_id(&conn, plan_id).unwrap();
+        assert_eq!(plan.status, PlanStatus::Paused);
+        assert!(plan.completed_at.is_none());
+
+        update_plan_status(&conn, plan_id, PlanStatus::Active, dt("2026-10-03 10:00:00")).unwrap();
+        let events: Vec<PlanEventType> = plan_event_dao::get_plan_events(&conn, plan_id).unwrap()
+            .iter().map(|e| e.event_type).collect();
+        assert_eq!(&events[events.len() - 4..], &[
+            PlanEventType::Completed, PlanEventType::Reopened, PlanEventType::Paused, PlanEventType::Resumed,
+        ]);
+    }
+
+    #[test]
+    fn inserted_day_pushes_later_open_days_back() {
+        let (conn, plan_id, days) = setup_days(4);
+        write(&conn, days[0], "2026-10-01 10:00:00");
+        write(&conn, days[2], "2026-10-01 11:00:00");
+
+        let req: AddPlanDayRequest = serde_json::from_value(serde_json::json!({
+            "plan_id": plan_id, "day_number": 2, "title": "插入", "prompt": "",
+        })).unwrap();
+        add_plan_day(&conn, &req).unwrap();
+        let plan = get_plan_with_days(&conn, plan_id).unwrap();
+        assert_eq!(schedule_of(&plan), vec![
+            (1, "2026-10-01"), (2, "2026-10-02"), (3, "2026-10-04"), (4, "2026-10-03"), (5, "2026-10-05"),
+        ]);
+        assert_eq!(plan.days[1].title, "插入");
+        assert_eq!(plan.plan.total_days, 5);
+
+        let req: AddPlanDayRequest = serde_json::from_value(serde_json::json!({
+            "plan_id": plan_id, "day_number": 6, "title": "末尾", "prompt": "",
+        })).unwrap();
+        add_plan_day(&conn, &req).unwrap();
+        let plan = get_plan_with_days(&conn, plan_id).unwrap();
+        assert_eq!(schedule_of(&plan).last(), Some(&(6, "2026-10-06")));
+    }
+}
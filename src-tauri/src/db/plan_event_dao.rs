@@ -0,0 +1,39 @@
+//! 写作计划生命周期事件数据库操作
+
+use rusqlite::{params, Connection};
+use crate::errors::AppResult;
+use crate::models::plan_event::*;
+
+/// 记录计划事件
+pub fn record_event(
+    conn: &Connection,
+    plan_id: i64,
+    event_type: PlanEventType,
+    occurred_at: &str,
+    reason: Option<PlanCompletionReason>,
+) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO plan_events (plan_id, event_type, occurred_at, reason) VALUES (?1, ?2, ?3, ?4)",
+        params![plan_id, event_type.as_str(), occurred_at, reason.as_ref().map(|r| r.as_str())],
+    )?;
+    Ok(())
+}
+
+/// 获取计划的生命周期历史（按时间升序）
+pub fn get_plan_events(conn: &Connection, plan_id: i64) -> AppResult<Vec<PlanEvent>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, plan_id, event_type, occurred_at, reason
+         FROM plan_events WHERE plan_id = ?1
+         ORDER BY id ASC"
+    )?;
+    let events = stmt.query_map(params![plan_id], |row| {
+        Ok(PlanEvent {
+            id: row.get(0)?,
+            plan_id: row.get(1)?,
+            event_type: PlanEventType::from_str(&row.get::<_, String>(2)?),
+            occurred_at: row.get(3)?,
+            reason: row.get::<_, Option<String>>(4)?.as_deref().and_then(PlanCompletionReason::from_str),
+        })
+    })?.collect::<Result<Vec<_>, _>>()?;
+    Ok(events)
+}
@@ -0,0 +1,109 @@
+//! 写作记录修订历史数据库操作
+
+use rusqlite::{params, Connection};
+use crate::errors::{AppError, AppResult};
+use crate::models::revision::*;
+use crate::models::writing::{UpdateWritingRequest, Writing};
+use crate::db::writing_dao;
+use crate::{text_diff, text_metrics};
+
+/// 以写作记录的当前内容记录一个新版本（与最新版本相同时跳过）
+pub fn record_revision(conn: &Connection, writing_id: i64) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO writing_revisions (writing_id, revision_number, title, content, word_count)
+         SELECT w.id,
+                COALESCE((SELECT MAX(revision_number) FROM writing_revisions WHERE writing_id = w.id), 0) + 1,
+                w.title, w.content, w.word_count
+         FROM writings w
+         WHERE w.id = ?1
+           AND NOT EXISTS (
+               SELECT 1 FROM writing_revisions r
+               WHERE r.writing_id = w.id AND r.title = w.title AND r.content = w.content
+                 AND r.revision_number = (SELECT MAX(revision_number) FROM writing_revisions WHERE writing_id = w.id)
+           )",
+        params![writing_id],
+    )?;
+    Ok(())
+}
+
+/// 获取写作记录的所有版本（最新的在前）
+pub fn get_revisions(conn: &Connection, writing_id: i64) -> AppResult<Vec<WritingRevision>> {
+    writing_dao::get_writing_by_id(conn, writing_id)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, writing_id, revision_number, title, content, word_count, created_at
+         FROM writing_revisions WHERE writing_id = ?1
+         ORDER BY revision_number DESC"
+    )?;
+    let revisions = stmt.query_map(params![writing_id], map_revision_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(revisions)
+}
+
+/// 根据 ID 获取版本
+pub fn get_revision_by_id(conn: &Connection, id: i64) -> AppResult<WritingRevision> {
+    conn.query_row(
+        "SELECT id, writing_id, revision_number, title, content, word_count, created_at
+         FROM writing_revisions WHERE id = ?1",
+        params![id],
+        map_revision_row,
+    ).map_err(|_| AppError::NotFound(format!("修订版本 ID {} 不存在", id)))
+}
+
+/// 比较两个版本（或某个版本与当前内容）的纯文本差异
+pub fn diff_revisions(conn: &Connection, req: &DiffRevisionsRequest) -> AppResult<RevisionDiff> {
+    let from = get_revision_by_id(conn, req.from_revision_id)?;
+    let to_content = match req.to_revision_id {
+        Some(id) => {
+            let to = get_revision_by_id(conn, id)?;
+            if to.writing_id != from.writing_id {
+                return Err(AppError::Business("只能比较同一篇写作的版本".to_string()));
+            }
+            to.content
+        }
+        None => writing_dao::get_writing_by_id(conn, from.writing_id)?.content,
+    };
+
+    let segments = text_diff::diff(
+        &text_metrics::html_to_text(&from.content),
+        &text_metrics::html_to_text(&to_content),
+        req.granularity,
+    );
+    let count = |op: DiffOp| segments.iter()
+        .filter(|s| s.op == op)
+        .map(|s| s.text.chars().filter(|c| !c.is_whitespace()).count() as i32)
+        .sum();
+
+    Ok(RevisionDiff {
+        from_revision_id: from.id,
+        to_revision_id: req.to_revision_id,
+        granularity: req.granularity,
+        inserted_chars: count(DiffOp::Insert),
+        deleted_chars: count(DiffOp::Delete),
+        segments,
+    })
+}
+
+/// 将旧版本恢复为写作记录的当前内容（恢复本身也会记录为一个新版本）
+pub fn restore_revision(conn: &Connection, revision_id: i64) -> AppResult<Writing> {
+    let revision = get_revision_by_id(conn, revision_id)?;
+    writing_dao::update_writing(conn, &UpdateWritingRequest {
+        id: revision.writing_id,
+        title: Some(revision.title),
+        content: Some(revision.content),
+        plan_day_id: None,
+        clear_plan_day: false,
+    })
+}
+
+fn map_revision_row(row: &rusqlite::Row) -> rusqlite::Result<WritingRevision> {
+    Ok(WritingRevision {
+        id: row.get(0)?,
+        writing_id: row.get(1)?,
+        revision_number: row.get(2)?,
+        title: row.get(3)?,
+        content: row.get(4)?,
+        word_count: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
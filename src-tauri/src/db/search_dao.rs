@@ -0,0 +1,374 @@
+//! 全文搜索
+//!
+//! 基于 FTS5 trigram 索引（见迁移 v9，写作正文索引去掉标签后的纯文本）。trigram 只能匹配 3 个字符及以上的词，
+//! 更短的词（如常见的两字中文词）改用同一索引上的 LIKE 子串匹配。
+//! 命中高亮和摘要在 Rust 端生成，长短词的展示效果保持一致。
+//!
+//! 各来源的 bm25 得分不在同一尺度上（只有短词时也没有得分），因此先在每个来源内排序，
+//! 再按来源内的名次交替合并。
+
+use rusqlite::{Connection, ToSql};
+use crate::db::task_dao::ACTIVE_LIST_FILTER;
+use crate::errors::AppResult;
+use crate::models::search::*;
+
+/// 高亮标记
+const MARK_START: &str = "<mark>";
+const MARK_END: &str = "</mark>";
+
+/// 摘要长度（字符数）
+const SNIPPET_CHARS: usize = 80;
+
+/// 摘要中命中位置之前保留的字符数
+const SNIPPET_LEAD_CHARS: usize = 20;
+
+/// trigram 分词器可匹配的最短词长
+const MIN_MATCH_CHARS: usize = 3;
+
+/// 一类可搜索内容：FTS 表及其对应的内容表
+struct SearchSource {
+    kind: SearchKind,
+    fts_table: &'static str,
+    content_table: &'static str,
+    title_column: &'static str,
+    body_column: &'static str,
+    date_column: &'static str,
+    /// 每日题目需要带出所属计划 ID
+    plan_id_column: &'static str,
+    /// 额外的过滤条件（作用于内容表的列）
+    filter: Option<&'static str>,
+}
+
+const SOURCES: &[SearchSource] = &[
+    SearchSource {
+        kind: SearchKind::Writing,
+        fts_table: "writings_fts",
+        content_table: "writings",
+        title_column: "title",
+        body_column: "plain_text",
+        date_column: "written_date",
+        plan_id_column: "NULL",
+        filter: None,
+    },
+    SearchSource {
+        kind: SearchKind::PlanDay,
+        fts_table: "plan_days_fts",
+        content_table: "plan_days",
+        title_column: "title",
+        body_column: "prompt",
+        date_column: "scheduled_date",
+        plan_id_column: "c.plan_id",
+        filter: None,
+    },
+    SearchSource {
+        kind: SearchKind::Task,
+        fts_table: "tasks_fts",
+        content_table: "tasks",
+        title_column: "title",
+        body_column: "description",
+        date_column: "due_date",
+        plan_id_column: "NULL",
+        filter: Some(ACTIVE_LIST_FILTER),
+    },
+];
+
+/// 搜索写作记录、每日题目和任务
+///
+/// 每个来源内按相关度排序，再按名次交替合并：各来源的第一名排在最前，依此类推；
+/// 名次相同时较新的排在前面。已归档列表中的任务不参与搜索。
+pub fn search(conn: &Connection, req: &SearchRequest) -> AppResult<Vec<SearchResult>> {
+    let terms: Vec<&str> = req.query.split_whitespace().collect();
+    if terms.is_empty() || req.limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    // (来源内名次, 结果)
+    let mut ranked: Vec<(usize, SearchResult)> = Vec::new();
+    for source in SOURCES {
+        if !req.kinds.is_empty() && !req.kinds.contains(&source.kind) {
+            continue;
+        }
+        ranked.extend(search_source(conn, source, &terms, req.limit)?.into_iter().enumerate());
+    }
+
+    // 排序稳定，名次和日期都相同时保持来源的先后顺序
+    ranked.sort_by(|(rank_a, a), (rank_b, b)| rank_a.cmp(rank_b).then_with(|| b.date.cmp(&a.date)));
+    ranked.truncate(req.limit);
+
+    Ok(ranked.into_iter().map(|(_, result)| result).collect())
+}
+
+/// 在单个来源中搜索，按相关度排序（相关度相同时较新的在前）
+fn search_source(conn: &Connection, source: &SearchSource, terms: &[&str], limit: usize) -> AppResult<Vec<SearchResult>> {
+    let fts = source.fts_table;
+    let mut conditions: Vec<String> = Vec::new();
+    let mut param_values: Vec<Box<dyn ToSql>> = Vec::new();
+
+    let (long_terms, short_terms): (Vec<&str>, Vec<&str>) =
+        terms.iter().partition(|t| t.chars().count() >= MIN_MATCH_CHARS);
+
+    if !long_terms.is_empty() {
+        conditions.push(format!("{} MATCH ?{}", fts, param_values.len() + 1));
+        param_values.push(Box::new(match_expression(&long_terms)));
+    }
+    for term in &short_terms {
+        let n = param_values.len() + 1;
+        conditions.push(format!(
+            "({fts}.{title} LIKE ?{n} ESCAPE '\\' OR {fts}.{body} LIKE ?{n} ESCAPE '\\')",
+            fts = fts, title = source.title_column, body = source.body_column, n = n
+        ));
+        param_values.push(Box::new(like_pattern(term)));
+    }
+    if let Some(filter) = source.filter {
+        conditions.push(filter.to_string());
+    }
+
+    // bm25 只在有 MATCH 条件时可用；标题命中的权重高于正文
+    let score = if long_terms.is_empty() {
+        "0.0".to_string()
+    } else {
+        format!("bm25({}, 4.0, 1.0)", fts)
+    };
+
+    let sql = format!(
+        "SELECT c.id, c.{title}, c.{body}, c.{date}, {plan_id}, {score}
+         FROM {fts}
+         INNER JOIN {table} c ON c.id = {fts}.rowid
+         WHERE {conditions}
+         ORDER BY 6 ASC, 4 DESC
+         LIMIT ?{limit}",
+        title = source.title_column,
+        body = source.body_column,
+        date = source.date_column,
+        plan_id = source.plan_id_column,
+        score = score,
+        fts = fts,
+        table = source.content_table,
+        conditions = conditions.join(" AND "),
+        limit = param_values.len() + 1,
+    );
+    param_values.push(Box::new(limit as i64));
+
+    let params_refs: Vec<&dyn ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_refs.as_slice(), |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<i64>>(4)?,
+            row.get::<_, f64>(5)?,
+        ))
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    let results = rows.into_iter().map(|(id, title, body, date, plan_id, score)| {
+        SearchResult {
+            kind: source.kind,
+            id,
+            title: highlight(&title, terms),
+            snippet: snippet(body.as_deref().unwrap_or(""), terms),
+            date,
+            plan_id,
+            score,
+        }
+    }).collect();
+
+    Ok(results)
+}
+
+/// 构造 FTS5 查询表达式：每个词作为短语，空格连接表示同时匹配
+fn match_expression(terms: &[&str]) -> String {
+    terms.iter()
+        .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 构造子串匹配的 LIKE 模式，转义通配符
+fn like_pattern(term: &str) -> String {
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// 在文本中查找所有命中区间（字符下标，左闭右开，互不重叠，不区分大小写）
+fn find_matches(text: &[char], terms: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        let matched = terms.iter()
+            .filter(|term| !term.is_empty() && i + term.len() <= text.len())
+            .filter(|term| term.iter().zip(&text[i..]).all(|(a, b)| a.to_lowercase().eq(b.to_lowercase())))
+            .map(|term| term.len())
+            .max();
+        match matched {
+            Some(len) => {
+                ranges.push((i, i + len));
+                i += len;
+            }
+            None => i += 1,
+        }
+    }
+    ranges
+}
+
+/// 用高亮标记包裹 `text[start..end]` 中的命中区间，其余文字做 HTML 转义
+fn mark_range(text: &[char], start: usize, end: usize, ranges: &[(usize, usize)]) -> String {
+    let mut out = String::new();
+    let mut pos = start;
+    for &(s, e) in ranges.iter().filter(|(s, e)| *s >= start && *e <= end) {
+        push_escaped(&mut out, &text[pos..s]);
+        out.push_str(MARK_START);
+        push_escaped(&mut out, &text[s..e]);
+        out.push_str(MARK_END);
+        pos = e;
+    }
+    push_escaped(&mut out, &text[pos..end]);
+    out
+}
+
+/// 追加 HTML 转义后的文字
+fn push_escaped(out: &mut String, text: &[char]) {
+    for &c in text {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+fn to_chars(terms: &[&str]) -> Vec<Vec<char>> {
+    terms.iter().map(|t| t.chars().collect()).collect()
+}
+
+/// 高亮整段文本（用于标题）
+fn highlight(text: &str, terms: &[&str]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let ranges = find_matches(&chars, &to_chars(terms));
+    mark_range(&chars, 0, chars.len(), &ranges)
+}
+
+/// 截取第一个命中位置附近的摘要并高亮；正文没有命中时取开头
+fn snippet(text: &str, terms: &[&str]) -> String {
+    let chars: Vec<char> = text.chars()
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .collect();
+    let ranges = find_matches(&chars, &to_chars(terms));
+
+    let first = ranges.first().map(|r| r.0).unwrap_or(0);
+    let end = (first.saturating_sub(SNIPPET_LEAD_CHARS) + SNIPPET_CHARS).min(chars.len());
+    let start = end.saturating_sub(SNIPPET_CHARS);
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.push_str(&mark_range(&chars, start, end, &ranges));
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::init_database;
+    use crate::db::task_dao;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        conn
+    }
+
+    fn add_writing(conn: &Connection, title: &str, text: &str, date: &str) {
+        conn.execute(
+            "INSERT INTO writings (title, content, plain_text, word_count, written_date) VALUES (?1, ?2, ?2, 0, ?3)",
+            rusqlite::params![title, text, date],
+        ).unwrap();
+    }
+
+    fn add_task(conn: &Connection, title: &str, list_id: Option<i64>) {
+        let req = serde_json::from_value(serde_json::json!({"title": title, "list_id": list_id})).unwrap();
+        task_dao::create_task(conn, &req).unwrap();
+    }
+
+    fn search_for(conn: &Connection, query: &str) -> Vec<SearchResult> {
+        search(conn, &SearchRequest { query: query.to_string(), kinds: Vec::new(), limit: 50 }).unwrap()
+    }
+
+    #[test]
+    fn two_character_chinese_query_matches_substring() {
+        let conn = setup();
+        add_writing(&conn, "周末", "今天去公园散步，看到很多人。", "2026-10-01");
+        add_writing(&conn, "读书", "读完了一本小说。", "2026-10-02");
+
+        let results = search_for(&conn, "公园");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "周末");
+        assert_eq!(results[0].snippet, "今天去<mark>公园</mark>散步，看到很多人。");
+    }
+
+    #[test]
+    fn three_character_chinese_query_uses_index() {
+        let conn = setup();
+        add_writing(&conn, "周末", "今天去公园散步。", "2026-10-01");
+        add_writing(&conn, "公园散步记", "傍晚又去了一次。", "2026-10-02");
+
+        let results = search_for(&conn, "公园散");
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.score < 0.0));
+        assert_eq!(results[0].title, "<mark>公园散</mark>步记");
+        assert_eq!(results[1].snippet, "今天去<mark>公园散</mark>步。");
+    }
+
+    #[test]
+    fn escapes_html_around_marks() {
+        let conn = setup();
+        add_task(&conn, "修复 <script> & \"引号\" 问题", None);
+
+        let results = search_for(&conn, "script");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "修复 &lt;<mark>script</mark>&gt; &amp; &quot;引号&quot; 问题");
+    }
+
+    #[test]
+    fn interleaves_sources_by_rank() {
+        let conn = setup();
+        for (i, date) in ["2026-10-01", "2026-10-02", "2026-10-03"].iter().enumerate() {
+            add_writing(&conn, &format!("散步记 {}", i), "散步记 散步记 散步记", date);
+        }
+        add_task(&conn, "整理散步记的照片，顺便把相册里其他很多很多的照片也一起整理一下", None);
+
+        let results = search_for(&conn, "散步记");
+        assert_eq!(results.len(), 4);
+        // 任务的 bm25 得分较差，但仍是任务来源的第一名，排在写作记录的第二名之前
+        let kinds: Vec<SearchKind> = results.iter().map(|r| r.kind).collect();
+        assert!(kinds[..2].contains(&SearchKind::Task));
+        assert!(kinds[..2].contains(&SearchKind::Writing));
+
+        // 只有短词时没有得分，同样按名次交替、名次相同时较新的在前
+        let results = search_for(&conn, "散步");
+        let kinds: Vec<SearchKind> = results.iter().map(|r| r.kind).collect();
+        assert_eq!(kinds, vec![SearchKind::Writing, SearchKind::Task, SearchKind::Writing, SearchKind::Writing]);
+        assert_eq!(results[0].date.as_deref(), Some("2026-10-03"));
+    }
+
+    #[test]
+    fn excludes_tasks_in_archived_lists() {
+        let conn = setup();
+        conn.execute("INSERT INTO task_lists (name, archived) VALUES ('旧项目', 1)", []).unwrap();
+        let archived = conn.last_insert_rowid();
+        add_task(&conn, "旧项目里的散步计划", Some(archived));
+        add_task(&conn, "新的散步计划", None);
+
+        let results = search_for(&conn, "散步计划");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "新的<mark>散步计划</mark>");
+    }
+}
@@ -0,0 +1,101 @@
+//! 应用设置数据库操作
+
+use rusqlite::{params, Connection, OptionalExtension};
+use chrono::{NaiveDate, NaiveTime};
+use crate::errors::{AppError, AppResult};
+use crate::models::settings::{PlanCompletionSettings, StreakSettings, WritingReminderSettings};
+
+/// 每日写作提醒设置的键
+const WRITING_REMINDER_KEY: &str = "writing_reminder";
+
+/// 连续写作统计方式的键
+const STREAK_KEY: &str = "streak";
+
+/// 计划自动完成设置的键
+const PLAN_COMPLETION_KEY: &str = "plan_completion";
+
+/// 读取设置值
+pub fn get_setting(conn: &Connection, key: &str) -> AppResult<Option<String>> {
+    let value = conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?1",
+        params![key],
+        |row| row.get(0),
+    ).optional()?;
+    Ok(value)
+}
+
+/// 写入设置值（不存在则插入）
+pub fn set_setting(conn: &Connection, key: &str, value: &str) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO app_settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now', 'localtime')",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+/// 获取每日写作提醒设置（未设置时返回默认值）
+pub fn get_writing_reminder_settings(conn: &Connection) -> AppResult<WritingReminderSettings> {
+    match get_setting(conn, WRITING_REMINDER_KEY)? {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(WritingReminderSettings::default()),
+    }
+}
+
+/// 保存每日写作提醒设置
+pub fn save_writing_reminder_settings(conn: &Connection, settings: &WritingReminderSettings) -> AppResult<WritingReminderSettings> {
+    for time in [&settings.remind_at, &settings.follow_up_at] {
+        NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|_| AppError::Business(format!("时间格式无效（应为 HH:MM）: {}", time)))?;
+    }
+    if settings.follow_up_enabled && settings.follow_up_at <= settings.remind_at {
+        return Err(AppError::Business("二次提醒时间需要晚于每日提醒时间".to_string()));
+    }
+
+    set_setting(conn, WRITING_REMINDER_KEY, &serde_json::to_string(settings)?)?;
+    get_writing_reminder_settings(conn)
+}
+
+/// 获取连续写作统计方式（未设置时返回默认值）
+pub fn get_streak_settings(conn: &Connection) -> AppResult<StreakSettings> {
+    match get_setting(conn, STREAK_KEY)? {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(StreakSettings::default()),
+    }
+}
+
+/// 保存连续写作统计方式
+pub fn save_streak_settings(conn: &Connection, settings: &StreakSettings) -> AppResult<StreakSettings> {
+    let mut settings = settings.clone();
+    if let Some(day) = settings.rest_weekdays.iter().find(|d| !(1..=7).contains(*d)) {
+        return Err(AppError::Business(format!("休息日无效（应为 1~7）: {}", day)));
+    }
+    if settings.rest_weekdays.len() >= 7 && (1..=7).all(|d| settings.rest_weekdays.contains(&d)) {
+        return Err(AppError::Business("不能把每天都设为休息日".to_string()));
+    }
+    for date in &settings.freeze_dates {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| AppError::Business(format!("日期格式无效（应为 YYYY-MM-DD）: {}", date)))?;
+    }
+    settings.rest_weekdays.sort_unstable();
+    settings.rest_weekdays.dedup();
+    settings.freeze_dates.sort();
+    settings.freeze_dates.dedup();
+
+    set_setting(conn, STREAK_KEY, &serde_json::to_string(&settings)?)?;
+    get_streak_settings(conn)
+}
+
+/// 获取计划自动完成设置（未设置时返回默认值）
+pub fn get_plan_completion_settings(conn: &Connection) -> AppResult<PlanCompletionSettings> {
+    match get_setting(conn, PLAN_COMPLETION_KEY)? {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => Ok(PlanCompletionSettings::default()),
+    }
+}
+
+/// 保存计划自动完成设置
+pub fn save_plan_completion_settings(conn: &Connection, settings: &PlanCompletionSettings) -> AppResult<PlanCompletionSettings> {
+    set_setting(conn, PLAN_COMPLETION_KEY, &serde_json::to_string(settings)?)?;
+    get_plan_completion_settings(conn)
+}
@@ -0,0 +1,145 @@
+//! 标签数据库操作
+
+use rusqlite::{params, Connection};
+use crate::db::name_rule::NameRule;
+use crate::errors::{AppError, AppResult};
+use crate::models::tag::*;
+
+/// 标签名称规则
+const TAG_NAME: NameRule = NameRule { table: "tags", label: "标签" };
+
+/// 获取所有标签（按名称排序）
+pub fn get_all_tags(conn: &Connection) -> AppResult<Vec<Tag>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, color, created_at FROM tags ORDER BY name COLLATE NOCASE ASC"
+    )?;
+
+    let tags = stmt.query_map([], map_tag_row)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(tags)
+}
+
+/// 根据 ID 获取标签
+pub fn get_tag_by_id(conn: &Connection, id: i64) -> AppResult<Tag> {
+    conn.query_row(
+        "SELECT id, name, color, created_at FROM tags WHERE id = ?1",
+        params![id],
+        map_tag_row,
+    ).map_err(|_| AppError::NotFound(format!("标签 ID {} 不存在", id)))
+}
+
+/// 创建标签（名称不区分大小写唯一）
+pub fn create_tag(conn: &Connection, req: &CreateTagRequest) -> AppResult<Tag> {
+    let name = TAG_NAME.validate(&req.name)?;
+    TAG_NAME.ensure_available(conn, &name, None)?;
+
+    conn.execute(
+        "INSERT INTO tags (name, color) VALUES (?1, ?2)",
+        params![name, req.color],
+    )?;
+    get_tag_by_id(conn, conn.last_insert_rowid())
+}
+
+/// 更新标签
+pub fn update_tag(conn: &Connection, req: &UpdateTagRequest) -> AppResult<Tag> {
+    get_tag_by_id(conn, req.id)?;
+
+    if let Some(ref name) = req.name {
+        let name = TAG_NAME.validate(name)?;
+        TAG_NAME.ensure_available(conn, &name, Some(req.id))?;
+        conn.execute("UPDATE tags SET name = ?1 WHERE id = ?2", params![name, req.id])?;
+    }
+    if let Some(ref color) = req.color {
+        conn.execute("UPDATE tags SET color = ?1 WHERE id = ?2", params![color, req.id])?;
+    }
+
+    get_tag_by_id(conn, req.id)
+}
+
+/// 删除标签（关联关系随外键级联删除）
+pub fn delete_tag(conn: &Connection, id: i64) -> AppResult<()> {
+    let affected = conn.execute("DELETE FROM tags WHERE id = ?1", params![id])?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("标签 ID {} 不存在", id)));
+    }
+    Ok(())
+}
+
+/// 设置任务的标签（整体替换）
+pub fn set_task_tags(conn: &Connection, req: &SetTagsRequest) -> AppResult<()> {
+    let exists: i32 = conn.query_row("SELECT COUNT(*) FROM tasks WHERE id = ?1", params![req.target_id], |row| row.get(0))?;
+    if exists == 0 {
+        return Err(AppError::NotFound(format!("任务 ID {} 不存在", req.target_id)));
+    }
+    replace_links(conn, "task_tags", "task_id", req)
+}
+
+/// 设置写作记录的标签（整体替换）
+pub fn set_writing_tags(conn: &Connection, req: &SetTagsRequest) -> AppResult<()> {
+    let exists: i32 = conn.query_row("SELECT COUNT(*) FROM writings WHERE id = ?1", params![req.target_id], |row| row.get(0))?;
+    if exists == 0 {
+        return Err(AppError::NotFound(format!("写作记录 ID {} 不存在", req.target_id)));
+    }
+    replace_links(conn, "writing_tags", "writing_id", req)
+}
+
+/// 获取各标签的使用统计（供统计面板使用）
+pub fn get_tag_stats(conn: &Connection) -> AppResult<Vec<TagStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT tg.id, tg.name, tg.color, tg.created_at,
+                (SELECT COUNT(*) FROM task_tags tt WHERE tt.tag_id = tg.id),
+                (SELECT COUNT(*) FROM task_tags tt
+                 INNER JOIN tasks t ON t.id = tt.task_id
+                 WHERE tt.tag_id = tg.id AND t.completed = 0),
+                (SELECT COUNT(*) FROM writing_tags wt WHERE wt.tag_id = tg.id),
+                (SELECT COALESCE(SUM(w.word_count), 0) FROM writing_tags wt
+                 INNER JOIN writings w ON w.id = wt.writing_id
+                 WHERE wt.tag_id = tg.id)
+         FROM tags tg
+         ORDER BY tg.name COLLATE NOCASE ASC"
+    )?;
+
+    let stats = stmt.query_map([], |row| {
+        Ok(TagStats {
+            tag: map_tag_row(row)?,
+            task_count: row.get(4)?,
+            open_task_count: row.get(5)?,
+            writing_count: row.get(6)?,
+            writing_words: row.get(7)?,
+        })
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    Ok(stats)
+}
+
+/// 解析 GROUP_CONCAT 得到的标签 ID 列表
+pub fn split_tag_ids(raw: Option<String>) -> Vec<i64> {
+    raw.map(|s| s.split(',').filter_map(|id| id.trim().parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+fn map_tag_row(row: &rusqlite::Row) -> rusqlite::Result<Tag> {
+    Ok(Tag {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        color: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+/// 在事务中整体替换关联表中的标签
+fn replace_links(conn: &Connection, table: &str, column: &str, req: &SetTagsRequest) -> AppResult<()> {
+    for tag_id in &req.tag_ids {
+        get_tag_by_id(conn, *tag_id)?;
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&format!("DELETE FROM {} WHERE {} = ?1", table, column), params![req.target_id])?;
+    for tag_id in &req.tag_ids {
+        tx.execute(
+            &format!("INSERT OR IGNORE INTO {} ({}, tag_id) VALUES (?1, ?2)", table, column),
+            params![req.target_id, tag_id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
@@ -1,127 +1,425 @@
 //! 任务（待办事项）数据库操作
 
 use rusqlite::{params, Connection};
+use rusqlite::types::Value;
+use chrono::{Datelike, Months, NaiveDate, NaiveDateTime, NaiveTime};
 use crate::errors::{AppError, AppResult};
-use crate::models::task::{Task, CreateTaskRequest, UpdateTaskRequest, Priority, RepeatType};
+use crate::models::task::{
+    Task, TaskCompletion, CreateTaskRequest, UpdateTaskRequest, PreviewRRuleRequest, Priority, RepeatType,
+    TaskReminder, ReminderKind, SetTaskRemindersRequest, DueReminder,
+    TaskItem, TaskDetail, AddTaskItemRequest, UpdateTaskItemRequest, ReorderTaskItemsRequest,
+    TaskBuckets, RollOverTasksRequest, TaskQuery, TaskPage, TaskSort, TaskSortKey, RepeatFilter,
+};
+use crate::recurrence::{self, RRule};
+use crate::models::task_event::TaskEventType;
+use crate::db::{tag_dao, task_event_dao, task_list_dao};
+
+/// 任务查询的列（顺序与 [`map_task_row`] 对应）
+const TASK_COLUMNS: &str =
+    "id, title, description, priority, due_date, repeat, completed, created_at, updated_at, rrule, exdates, due_time,
+     auto_complete,
+     (SELECT COUNT(*) FROM task_items i WHERE i.task_id = tasks.id),
+     (SELECT COUNT(*) FROM task_items i WHERE i.task_id = tasks.id AND i.completed = 1),
+     (SELECT GROUP_CONCAT(tt.tag_id) FROM task_tags tt WHERE tt.task_id = tasks.id),
+     list_id, completed_at";
+
+/// 排除已归档列表中任务的查询条件
+pub const ACTIVE_LIST_FILTER: &str =
+    "(list_id IS NULL OR list_id NOT IN (SELECT id FROM task_lists WHERE archived = 1))";
+
+/// 只有截止日期、没有截止时间的任务，提醒按这个时刻计算
+const DEFAULT_DUE_TIME: &str = "09:00";
+
+/// 错过的提醒在这个时间窗口内仍会补发（分钟），更早的直接跳过
+const MISSED_REMINDER_GRACE_MINUTES: i64 = 60;
+
+/// 将查询结果行映射为任务实体
+fn map_task_row(row: &rusqlite::Row) -> rusqlite::Result<Task> {
+    let item_count: i32 = row.get(13)?;
+    let completed_item_count: i32 = row.get(14)?;
+    Ok(Task {
+        id: Some(row.get(0)?),
+        title: row.get(1)?,
+        description: row.get(2)?,
+        priority: Priority::from_str(&row.get::<_, String>(3)?),
+        due_date: row.get::<_, Option<String>>(4)?.and_then(|s| chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
+        repeat: row.get::<_, Option<String>>(5)?.and_then(|s| RepeatType::from_str(&s)),
+        completed: row.get::<_, i32>(6)? != 0,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+        rrule: row.get(9)?,
+        exdates: split_exdates(row.get::<_, Option<String>>(10)?),
+        due_time: row.get(11)?,
+        auto_complete: row.get::<_, i32>(12)? != 0,
+        item_count,
+        completed_item_count,
+        progress: (item_count > 0).then(|| completed_item_count as f64 / item_count as f64),
+        tag_ids: tag_dao::split_tag_ids(row.get(15)?),
+        list_id: row.get(16)?,
+        completed_at: row.get(17)?,
+    })
+}
 
 /// 创建新任务
 pub fn create_task(conn: &Connection, req: &CreateTaskRequest) -> AppResult<Task> {
+    let rrule = validate_rrule(req.rrule.as_deref())?;
+    let exdates = join_exdates(&req.exdates)?;
+    let due_time = validate_time(req.due_time.as_deref())?;
+    let repeating = req.repeat.is_some() || rrule.is_some();
+    if let Some(list_id) = req.list_id {
+        task_list_dao::get_task_list_by_id(conn, list_id)?;
+    }
+
     conn.execute(
-        "INSERT INTO tasks (title, description, priority, due_date, repeat) VALUES (?1, ?2, ?3, ?4, ?5)",
+        "INSERT INTO tasks (title, description, priority, due_date, repeat, repeat_anchor, rrule, exdates, due_time, auto_complete, list_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         params![
             req.title,
             req.description,
             req.priority.as_str(),
             req.due_date,
             req.repeat.as_ref().map(|r| r.as_str()),
+            if repeating { req.due_date.as_ref() } else { None },
+            rrule,
+            exdates,
+            due_time,
+            req.auto_complete as i32,
+            req.list_id,
         ],
     )?;
 
     let id = conn.last_insert_rowid();
+    task_event_dao::record_event(conn, id, TaskEventType::Created, req.due_date.as_deref(), None)?;
     get_task_by_id(conn, id)
 }
 
 /// 根据 ID 获取任务
 pub fn get_task_by_id(conn: &Connection, id: i64) -> AppResult<Task> {
-    let mut stmt = conn.prepare(
-        "SELECT id, title, description, priority, due_date, repeat, completed, created_at, updated_at
-         FROM tasks WHERE id = ?1"
-    )?;
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS))?;
 
-    stmt.query_row(params![id], |row| {
-        Ok(Task {
-            id: Some(row.get(0)?),
-            title: row.get(1)?,
-            description: row.get(2)?,
-            priority: Priority::from_str(&row.get::<_, String>(3)?),
-            due_date: row.get::<_, Option<String>>(4)?.and_then(|s| chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
-            repeat: row.get::<_, Option<String>>(5)?.and_then(|s| RepeatType::from_str(&s)),
-            completed: row.get::<_, i32>(6)? != 0,
-            created_at: row.get(7)?,
-            updated_at: row.get(8)?,
-        })
-    }).map_err(|_| AppError::NotFound(format!("任务 ID {} 不存在", id)))
+    stmt.query_row(params![id], map_task_row)
+        .map_err(|_| AppError::NotFound(format!("任务 ID {} 不存在", id)))
 }
 
-/// 获取所有任务
+/// 获取所有任务（不含已归档列表中的任务）
 pub fn get_all_tasks(conn: &Connection) -> AppResult<Vec<Task>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, title, description, priority, due_date, repeat, completed, created_at, updated_at
-         FROM tasks ORDER BY completed ASC, due_date ASC, priority DESC, created_at DESC"
-    )?;
+    select_tasks(conn, &TaskQuery::default())
+}
 
-    let tasks = stmt.query_map([], |row| {
-        Ok(Task {
-            id: Some(row.get(0)?),
-            title: row.get(1)?,
-            description: row.get(2)?,
-            priority: Priority::from_str(&row.get::<_, String>(3)?),
-            due_date: row.get::<_, Option<String>>(4)?.and_then(|s| chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
-            repeat: row.get::<_, Option<String>>(5)?.and_then(|s| RepeatType::from_str(&s)),
-            completed: row.get::<_, i32>(6)? != 0,
-            created_at: row.get(7)?,
-            updated_at: row.get(8)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+/// 获取包含任一指定标签的任务（排序与 [`get_all_tasks`] 一致）
+pub fn get_tasks_by_tags(conn: &Connection, tag_ids: &[i64]) -> AppResult<Vec<Task>> {
+    select_tasks(conn, &TaskQuery { tag_ids: tag_ids.to_vec(), ..Default::default() })
+}
 
-    Ok(tasks)
+/// 获取指定列表中的任务（`list_id` 为空时返回收件箱中未归属列表的任务）
+pub fn get_tasks_by_list(conn: &Connection, list_id: Option<i64>) -> AppResult<Vec<Task>> {
+    if let Some(list_id) = list_id {
+        task_list_dao::get_task_list_by_id(conn, list_id)?;
+    }
+
+    select_tasks(conn, &TaskQuery { list_id, inbox: list_id.is_none(), ..Default::default() })
 }
 
-/// 获取今日待办任务
+/// 获取今日待办任务（不含已归档列表中的任务）
 pub fn get_today_tasks(conn: &Connection) -> AppResult<Vec<Task>> {
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let mut stmt = conn.prepare(
-        "SELECT id, title, description, priority, due_date, repeat, completed, created_at, updated_at
-         FROM tasks
-         WHERE (due_date = ?1 OR due_date IS NULL)
-         ORDER BY completed ASC, priority DESC, created_at DESC"
+    select_tasks(conn, &TaskQuery {
+        due_from: Some(today.clone()),
+        due_to: Some(today),
+        include_undated: true,
+        sort: vec![
+            TaskSort { key: TaskSortKey::Completed, descending: false },
+            TaskSort { key: TaskSortKey::Priority, descending: true },
+            TaskSort { key: TaskSortKey::CreatedAt, descending: true },
+        ],
+        ..Default::default()
+    })
+}
+
+/// 按条件查询任务，支持筛选、排序和分页
+///
+/// 未指定列表时不含已归档列表中的任务。
+pub fn query_tasks(conn: &Connection, query: &TaskQuery) -> AppResult<TaskPage> {
+    if let Some(list_id) = query.list_id {
+        task_list_dao::get_task_list_by_id(conn, list_id)?;
+    }
+
+    let (filter, values) = build_task_filter(query)?;
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM tasks WHERE {}", filter),
+        rusqlite::params_from_iter(&values),
+        |row| row.get(0),
     )?;
 
-    let tasks = stmt.query_map(params![today], |row| {
-        Ok(Task {
-            id: Some(row.get(0)?),
-            title: row.get(1)?,
-            description: row.get(2)?,
-            priority: Priority::from_str(&row.get::<_, String>(3)?),
-            due_date: row.get::<_, Option<String>>(4)?.and_then(|s| chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()),
-            repeat: row.get::<_, Option<String>>(5)?.and_then(|s| RepeatType::from_str(&s)),
-            completed: row.get::<_, i32>(6)? != 0,
-            created_at: row.get(7)?,
-            updated_at: row.get(8)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+    Ok(TaskPage {
+        tasks: select_tasks(conn, query)?,
+        total,
+        offset: query.offset,
+        limit: query.limit,
+    })
+}
+
+/// 执行任务查询（不计算总数）
+fn select_tasks(conn: &Connection, query: &TaskQuery) -> AppResult<Vec<Task>> {
+    let (filter, mut values) = build_task_filter(query)?;
+
+    let mut sql = format!("SELECT {} FROM tasks WHERE {} ORDER BY {}", TASK_COLUMNS, filter, task_order_by(&query.sort));
+    if query.limit.is_some() || query.offset > 0 {
+        // SQLite 中 LIMIT -1 表示不限制条数
+        sql.push_str(" LIMIT ? OFFSET ?");
+        values.push(Value::Integer(query.limit.map(i64::from).unwrap_or(-1)));
+        values.push(Value::Integer(query.offset as i64));
+    }
+
+    let mut stmt = conn.prepare(&sql)?;
+    let tasks = stmt.query_map(rusqlite::params_from_iter(&values), map_task_row)?
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(tasks)
 }
 
+/// 把查询条件转换为 WHERE 子句和对应的参数
+fn build_task_filter(query: &TaskQuery) -> AppResult<(String, Vec<Value>)> {
+    let mut clauses: Vec<String> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(list_id) = query.list_id {
+        clauses.push("list_id = ?".to_string());
+        values.push(Value::Integer(list_id));
+    } else if query.inbox {
+        clauses.push("list_id IS NULL".to_string());
+    } else {
+        clauses.push(ACTIVE_LIST_FILTER.to_string());
+    }
+
+    if let Some(completed) = query.completed {
+        clauses.push("completed = ?".to_string());
+        values.push(Value::Integer(completed as i64));
+    }
+
+    if !query.priorities.is_empty() {
+        clauses.push(format!("priority IN ({})", vec!["?"; query.priorities.len()].join(", ")));
+        values.extend(query.priorities.iter().map(|p| Value::Text(p.as_str().to_string())));
+    }
+
+    let due_from = query.due_from.as_deref().map(parse_query_date).transpose()?;
+    let due_to = query.due_to.as_deref().map(parse_query_date).transpose()?;
+    if due_from.is_some() || due_to.is_some() {
+        let mut range = vec!["due_date IS NOT NULL".to_string()];
+        if let Some(from) = due_from {
+            range.push("due_date >= ?".to_string());
+            values.push(Value::Text(from));
+        }
+        if let Some(to) = due_to {
+            range.push("due_date <= ?".to_string());
+            values.push(Value::Text(to));
+        }
+        let range = range.join(" AND ");
+        clauses.push(if query.include_undated {
+            format!("(({}) OR due_date IS NULL)", range)
+        } else {
+            format!("({})", range)
+        });
+    }
+
+    if let Some(repeat) = query.repeat {
+        clauses.push(match repeat {
+            RepeatFilter::None => "(repeat IS NULL AND rrule IS NULL)",
+            RepeatFilter::Any => "(repeat IS NOT NULL OR rrule IS NOT NULL)",
+            RepeatFilter::Daily => "(repeat = 'daily' AND rrule IS NULL)",
+            RepeatFilter::Weekly => "(repeat = 'weekly' AND rrule IS NULL)",
+            RepeatFilter::Monthly => "(repeat = 'monthly' AND rrule IS NULL)",
+            RepeatFilter::Custom => "rrule IS NOT NULL",
+        }.to_string());
+    }
+
+    if let Some(text) = query.text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        let pattern = format!(
+            "%{}%",
+            text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+        );
+        clauses.push("(title LIKE ? ESCAPE '\\' OR IFNULL(description, '') LIKE ? ESCAPE '\\')".to_string());
+        values.push(Value::Text(pattern.clone()));
+        values.push(Value::Text(pattern));
+    }
+
+    if !query.tag_ids.is_empty() {
+        clauses.push(format!(
+            "id IN (SELECT task_id FROM task_tags WHERE tag_id IN ({}))",
+            vec!["?"; query.tag_ids.len()].join(", ")
+        ));
+        values.extend(query.tag_ids.iter().map(|id| Value::Integer(*id)));
+    }
+
+    Ok((clauses.join(" AND "), values))
+}
+
+/// 生成 ORDER BY 子句，最后按 ID 排序以保证分页稳定
+fn task_order_by(sort: &[TaskSort]) -> String {
+    let default_sort = [
+        TaskSort { key: TaskSortKey::Completed, descending: false },
+        TaskSort { key: TaskSortKey::DueDate, descending: false },
+        TaskSort { key: TaskSortKey::Priority, descending: true },
+        TaskSort { key: TaskSortKey::CreatedAt, descending: true },
+    ];
+    let sort = if sort.is_empty() { &default_sort[..] } else { sort };
+
+    let mut terms: Vec<String> = sort.iter().map(|s| {
+        let dir = if s.descending { "DESC" } else { "ASC" };
+        match s.key {
+            TaskSortKey::Completed => format!("completed {}", dir),
+            TaskSortKey::DueDate => format!(
+                "due_date IS NULL, due_date {dir}, due_time IS NULL, due_time {dir}",
+                dir = dir
+            ),
+            TaskSortKey::Priority => format!(
+                "CASE priority WHEN 'high' THEN 2 WHEN 'medium' THEN 1 ELSE 0 END {}",
+                dir
+            ),
+            TaskSortKey::CreatedAt => format!("created_at {}", dir),
+            TaskSortKey::UpdatedAt => format!("updated_at {}", dir),
+            TaskSortKey::CompletedAt => format!("completed_at IS NULL, completed_at {}", dir),
+            TaskSortKey::Title => format!("title COLLATE NOCASE {}", dir),
+        }
+    }).collect();
+    terms.push("id ASC".to_string());
+    terms.join(", ")
+}
+
+fn parse_query_date(s: &str) -> AppResult<String> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| AppError::Business(format!("日期格式无效: {}", s)))
+}
+
+/// 获取按截止日期分组的任务（不含已归档列表中的任务）
+///
+/// 默认只包含未完成的任务；组内按截止日期、截止时间、优先级排序。
+pub fn get_task_buckets(conn: &Connection, today: NaiveDate, include_completed: bool) -> AppResult<TaskBuckets> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM tasks
+         WHERE {} AND (?1 OR completed = 0)
+         ORDER BY due_date ASC, due_time IS NULL, due_time ASC,
+                  CASE priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END,
+                  created_at DESC",
+        TASK_COLUMNS, ACTIVE_LIST_FILTER
+    ))?;
+    let tasks = stmt.query_map(params![include_completed], map_task_row)?.collect::<Result<Vec<_>, _>>()?;
+
+    let tomorrow = today + chrono::Duration::days(1);
+    let week_end = today + chrono::Duration::days(6 - today.weekday().num_days_from_monday() as i64);
+
+    let mut buckets = TaskBuckets::default();
+    for task in tasks {
+        let bucket = match task.due_date {
+            None => &mut buckets.no_date,
+            Some(d) if d < today => &mut buckets.overdue,
+            Some(d) if d == today => &mut buckets.today,
+            Some(d) if d == tomorrow => &mut buckets.tomorrow,
+            Some(d) if d <= week_end => &mut buckets.this_week,
+            Some(_) => &mut buckets.later,
+        };
+        bucket.push(task);
+    }
+    Ok(buckets)
+}
+
+/// 把逾期未完成的任务顺延到今天，返回被顺延的任务
+///
+/// 重复任务只移动本次的截止日期，不改变重复锚点，之后的发生日期保持原来的规律。
+pub fn roll_over_overdue(conn: &Connection, today: NaiveDate, req: &RollOverTasksRequest) -> AppResult<Vec<Task>> {
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, due_date FROM tasks
+         WHERE completed = 0 AND due_date < ?1 AND {}
+         ORDER BY due_date ASC, id ASC",
+        ACTIVE_LIST_FILTER
+    ))?;
+    let overdue: Vec<(i64, String)> = stmt.query_map(params![today_str], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    let overdue: Vec<(i64, String)> = match req.task_ids {
+        Some(ref ids) => overdue.into_iter().filter(|(id, _)| ids.contains(id)).collect(),
+        None => overdue,
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    for (id, due_date) in &overdue {
+        tx.execute(
+            "UPDATE tasks SET due_date = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2",
+            params![today_str, id],
+        )?;
+        task_event_dao::record_event(&tx, *id, TaskEventType::Rescheduled, Some(&today_str), Some(due_date))?;
+    }
+    tx.commit()?;
+
+    overdue.iter().map(|(id, _)| get_task_by_id(conn, *id)).collect()
+}
+
 /// 更新任务
+///
+/// 先校验截止时间、RRULE 和例外日期，再在一个事务中写入所有字段，
+/// 校验失败时任务保持不变。
 pub fn update_task(conn: &Connection, req: &UpdateTaskRequest) -> AppResult<Task> {
     // 先确认存在
-    get_task_by_id(conn, req.id)?;
+    let original = get_task_by_id(conn, req.id)?;
+    let due_time = req.due_time.as_deref().map(|t| validate_time(Some(t))).transpose()?;
+    let rrule = req.rrule.as_deref().map(|r| validate_rrule(Some(r))).transpose()?;
+    let exdates = req.exdates.as_deref().map(join_exdates).transpose()?;
 
+    let tx = conn.unchecked_transaction()?;
     if let Some(ref title) = req.title {
-        conn.execute("UPDATE tasks SET title = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![title, req.id])?;
+        tx.execute("UPDATE tasks SET title = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![title, req.id])?;
     }
     if let Some(ref desc) = req.description {
-        conn.execute("UPDATE tasks SET description = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![desc, req.id])?;
+        tx.execute("UPDATE tasks SET description = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![desc, req.id])?;
     }
     if let Some(ref priority) = req.priority {
-        conn.execute("UPDATE tasks SET priority = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![priority.as_str(), req.id])?;
+        tx.execute("UPDATE tasks SET priority = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![priority.as_str(), req.id])?;
     }
     if let Some(ref due_date) = req.due_date {
-        conn.execute("UPDATE tasks SET due_date = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![due_date, req.id])?;
+        tx.execute("UPDATE tasks SET due_date = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![due_date, req.id])?;
+        let previous = original.due_date.map(|d| d.to_string());
+        if previous.as_deref() != Some(due_date.as_str()) {
+            task_event_dao::record_event(&tx, req.id, TaskEventType::Rescheduled, Some(due_date), previous.as_deref())?;
+        }
+    }
+    if let Some(ref due_time) = due_time {
+        tx.execute("UPDATE tasks SET due_time = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![due_time, req.id])?;
     }
     if let Some(ref repeat) = req.repeat {
-        conn.execute("UPDATE tasks SET repeat = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![repeat.as_str(), req.id])?;
+        tx.execute("UPDATE tasks SET repeat = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![repeat.as_str(), req.id])?;
+    }
+    if let Some(ref rrule) = rrule {
+        tx.execute("UPDATE tasks SET rrule = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![rrule, req.id])?;
+    }
+    if let Some(ref exdates) = exdates {
+        tx.execute("UPDATE tasks SET exdates = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![exdates, req.id])?;
+    }
+    if let Some(auto_complete) = req.auto_complete {
+        tx.execute("UPDATE tasks SET auto_complete = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![auto_complete as i32, req.id])?;
+    }
+    // 截止日期或重复规则确实变化后，以新的截止日期作为重复锚点（COUNT 从头计数）
+    let previous_due = original.due_date.map(|d| d.to_string());
+    let due_changed = req.due_date.as_deref().is_some_and(|d| previous_due.as_deref() != Some(d));
+    let repeat_changed = req.repeat.as_ref().is_some_and(|r| original.repeat.as_ref() != Some(r));
+    let rrule_changed = rrule.as_ref().is_some_and(|r| *r != original.rrule);
+    if due_changed || repeat_changed || rrule_changed {
+        tx.execute("UPDATE tasks SET repeat_anchor = due_date WHERE id = ?1", params![req.id])?;
     }
+    tx.commit()?;
+
     if let Some(completed) = req.completed {
-        conn.execute("UPDATE tasks SET completed = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![completed as i32, req.id])?;
+        let task = get_task_by_id(conn, req.id)?;
+        if completed && !task.completed && task.is_repeating() {
+            return complete_occurrence(conn, &task, chrono::Local::now().date_naive());
+        }
+        set_completed(conn, &task, completed)?;
     }
 
     get_task_by_id(conn, req.id)
 }
 
-/// 删除任务
+/// 删除任务（清单项、完成记录和提醒随外键级联删除）
 pub fn delete_task(conn: &Connection, id: i64) -> AppResult<()> {
     let affected = conn.execute("DELETE FROM tasks WHERE id = ?1", params![id])?;
     if affected == 0 {
@@ -131,16 +429,459 @@ pub fn delete_task(conn: &Connection, id: i64) -> AppResult<()> {
 }
 
 /// 切换任务完成状态
+///
+/// 重复任务完成时不会标记为已完成，而是记录本次完成并把截止日期顺延到下一次。
 pub fn toggle_task(conn: &Connection, id: i64) -> AppResult<Task> {
     let task = get_task_by_id(conn, id)?;
-    let new_completed = !task.completed;
-    conn.execute(
-        "UPDATE tasks SET completed = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2",
-        params![new_completed as i32, id],
+    if !task.completed && task.is_repeating() {
+        return complete_occurrence(conn, &task, chrono::Local::now().date_naive());
+    }
+    set_completed(conn, &task, !task.completed)?;
+    get_task_by_id(conn, id)
+}
+
+/// 设置任务的完成状态，同时维护完成时间并记录完成 / 重新打开事件
+///
+/// 状态没有变化时不做任何操作。重复任务的单次完成见 [`complete_occurrence`]。
+fn set_completed(conn: &Connection, task: &Task, completed: bool) -> AppResult<()> {
+    let id = task.id.ok_or_else(|| AppError::Business("任务缺少 ID".to_string()))?;
+    if task.completed == completed {
+        return Ok(());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "UPDATE tasks SET completed = ?1,
+                          completed_at = CASE WHEN ?1 = 1 THEN datetime('now', 'localtime') END,
+                          updated_at = datetime('now', 'localtime')
+         WHERE id = ?2",
+        params![completed as i32, id],
     )?;
+    let event = if completed { TaskEventType::Completed } else { TaskEventType::Reopened };
+    let due_date = task.due_date.map(|d| d.to_string());
+    task_event_dao::record_event(&tx, id, event, due_date.as_deref(), None)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// 完成重复任务的一次发生：写入完成记录并顺延截止日期
+///
+/// RRULE 已无后续发生（COUNT / UNTIL 用尽）时，任务本身标记为已完成。
+fn complete_occurrence(conn: &Connection, task: &Task, today: NaiveDate) -> AppResult<Task> {
+    let id = task.id.ok_or_else(|| AppError::Business("任务缺少 ID".to_string()))?;
+
+    let occurrence = task.due_date.unwrap_or(today);
+    let anchor = conn.query_row(
+        "SELECT repeat_anchor FROM tasks WHERE id = ?1",
+        params![id],
+        |row| row.get::<_, Option<String>>(0),
+    )?
+    .and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok())
+    .unwrap_or(occurrence);
+
+    let next = if let Some(ref rule) = task.rrule {
+        let rule = RRule::parse(rule)?;
+        let exdates = recurrence::parse_exdates(&task.exdates)?;
+        // 与固定类型一致：晚于本次截止日期，且不早于今天
+        let after = occurrence.max(today.pred_opt().unwrap_or(today));
+        rule.occurrences(anchor, &exdates, Some(after), 1).first().copied()
+    } else if let Some(ref repeat) = task.repeat {
+        Some(next_due_date(repeat, anchor, occurrence, today))
+    } else {
+        return Err(AppError::Business(format!("任务 ID {} 不是重复任务", id)));
+    };
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO task_completions (task_id, occurrence_date) VALUES (?1, ?2)",
+        params![id, occurrence.to_string()],
+    )?;
+    task_event_dao::record_event(&tx, id, TaskEventType::Completed, Some(&occurrence.to_string()), None)?;
+    // 清单属于单次发生，进入下一次时重置
+    if next.is_some() {
+        tx.execute("UPDATE task_items SET completed = 0 WHERE task_id = ?1", params![id])?;
+    }
+    match next {
+        Some(next) => tx.execute(
+            "UPDATE tasks SET due_date = ?1, repeat_anchor = ?2, completed = 0, updated_at = datetime('now', 'localtime') WHERE id = ?3",
+            params![next.to_string(), anchor.to_string(), id],
+        )?,
+        None => tx.execute(
+            "UPDATE tasks SET repeat_anchor = ?1, completed = 1, completed_at = datetime('now', 'localtime'),
+                              updated_at = datetime('now', 'localtime')
+             WHERE id = ?2",
+            params![anchor.to_string(), id],
+        )?,
+    };
+    tx.commit()?;
+
     get_task_by_id(conn, id)
 }
 
+/// 计算重复任务的下一个截止日期
+///
+/// 所有发生日期都从 `anchor` 推算：按月重复时超出当月天数的日号取当月最后一天，
+/// 下个月再恢复原日号（1/31 → 2/28 或闰年 2/29 → 3/31）。
+/// 结果必须晚于 `after`，且不早于 `today`，逾期很久的任务会直接跳到今天及以后。
+pub fn next_due_date(repeat: &RepeatType, anchor: NaiveDate, after: NaiveDate, today: NaiveDate) -> NaiveDate {
+    let nth = |n: u32| -> Option<NaiveDate> {
+        match repeat {
+            RepeatType::Daily => anchor.checked_add_days(chrono::Days::new(n as u64)),
+            RepeatType::Weekly => anchor.checked_add_days(chrono::Days::new(7 * n as u64)),
+            RepeatType::Monthly => anchor.checked_add_months(Months::new(n)),
+        }
+    };
+
+    let mut n = 1;
+    while let Some(candidate) = nth(n) {
+        if candidate > after && candidate >= today {
+            return candidate;
+        }
+        n += 1;
+    }
+    // 超出 chrono 可表示的日期范围，保持原截止日期
+    after
+}
+
+/// 获取重复任务的完成历史（最近的在前）
+pub fn get_task_completions(conn: &Connection, task_id: i64) -> AppResult<Vec<TaskCompletion>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, task_id, occurrence_date, completed_at
+         FROM task_completions WHERE task_id = ?1
+         ORDER BY occurrence_date DESC, completed_at DESC"
+    )?;
+
+    let completions = stmt.query_map(params![task_id], |row| {
+        Ok(TaskCompletion {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            occurrence_date: row.get(2)?,
+            completed_at: row.get(3)?,
+        })
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    Ok(completions)
+}
+
+/// 预览重复规则接下来的若干次发生日期
+pub fn preview_rrule(req: &PreviewRRuleRequest) -> AppResult<Vec<String>> {
+    let rule = RRule::parse(&req.rrule)?;
+    let start = NaiveDate::parse_from_str(&req.start_date, "%Y-%m-%d")
+        .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)))?;
+    let exdates = recurrence::parse_exdates(&req.exdates)?;
+
+    Ok(rule
+        .occurrences(start, &exdates, None, req.count.min(366))
+        .into_iter()
+        .map(|d| d.to_string())
+        .collect())
+}
+
+/// 获取任务详情（含清单项）
+pub fn get_task_detail(conn: &Connection, task_id: i64) -> AppResult<TaskDetail> {
+    let task = get_task_by_id(conn, task_id)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, task_id, title, completed, sort_order, created_at
+         FROM task_items WHERE task_id = ?1
+         ORDER BY sort_order ASC, id ASC"
+    )?;
+    let items = stmt.query_map(params![task_id], |row| {
+        Ok(TaskItem {
+            id: Some(row.get(0)?),
+            task_id: row.get(1)?,
+            title: row.get(2)?,
+            completed: row.get::<_, i32>(3)? != 0,
+            sort_order: row.get(4)?,
+            created_at: row.get(5)?,
+        })
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    Ok(TaskDetail { task, items })
+}
+
+/// 添加清单项（追加到末尾）
+pub fn add_task_item(conn: &Connection, req: &AddTaskItemRequest) -> AppResult<TaskDetail> {
+    let task = get_task_by_id(conn, req.task_id)?;
+    if req.title.trim().is_empty() {
+        return Err(AppError::Business("清单项标题不能为空".to_string()));
+    }
+
+    conn.execute(
+        "INSERT INTO task_items (task_id, title, sort_order)
+         VALUES (?1, ?2, (SELECT COALESCE(MAX(sort_order) + 1, 0) FROM task_items WHERE task_id = ?1))",
+        params![req.task_id, req.title.trim()],
+    )?;
+
+    // 新增未完成项后，自动完成的任务需要重新打开
+    sync_auto_complete(conn, &task)?;
+    get_task_detail(conn, req.task_id)
+}
+
+/// 更新清单项（标题 / 完成状态）
+pub fn update_task_item(conn: &Connection, req: &UpdateTaskItemRequest) -> AppResult<TaskDetail> {
+    let task_id = get_item_task_id(conn, req.id)?;
+
+    if let Some(ref title) = req.title {
+        conn.execute("UPDATE task_items SET title = ?1 WHERE id = ?2", params![title.trim(), req.id])?;
+    }
+    if let Some(completed) = req.completed {
+        conn.execute("UPDATE task_items SET completed = ?1 WHERE id = ?2", params![completed as i32, req.id])?;
+    }
+
+    let task = get_task_by_id(conn, task_id)?;
+    sync_auto_complete(conn, &task)?;
+    get_task_detail(conn, task_id)
+}
+
+/// 切换清单项完成状态
+pub fn toggle_task_item(conn: &Connection, item_id: i64) -> AppResult<TaskDetail> {
+    let completed: bool = conn.query_row(
+        "SELECT completed FROM task_items WHERE id = ?1",
+        params![item_id],
+        |row| row.get::<_, i32>(0),
+    ).map_err(|_| AppError::NotFound(format!("清单项 ID {} 不存在", item_id)))? != 0;
+
+    update_task_item(conn, &UpdateTaskItemRequest { id: item_id, title: None, completed: Some(!completed) })
+}
+
+/// 删除清单项（后续项自动前移）
+pub fn delete_task_item(conn: &Connection, item_id: i64) -> AppResult<TaskDetail> {
+    let task_id = get_item_task_id(conn, item_id)?;
+    conn.execute("DELETE FROM task_items WHERE id = ?1", params![item_id])?;
+    renumber_task_items(conn, task_id)?;
+
+    let task = get_task_by_id(conn, task_id)?;
+    sync_auto_complete(conn, &task)?;
+    get_task_detail(conn, task_id)
+}
+
+/// 按给定顺序重排清单项
+pub fn reorder_task_items(conn: &Connection, req: &ReorderTaskItemsRequest) -> AppResult<TaskDetail> {
+    get_task_by_id(conn, req.task_id)?;
+
+    let tx = conn.unchecked_transaction()?;
+    // 先整体后移，请求中未包含的项自然排到末尾
+    tx.execute(
+        "UPDATE task_items SET sort_order = sort_order + ?1 WHERE task_id = ?2",
+        params![req.item_ids.len() as i32, req.task_id],
+    )?;
+    for (i, item_id) in req.item_ids.iter().enumerate() {
+        let affected = tx.execute(
+            "UPDATE task_items SET sort_order = ?1 WHERE id = ?2 AND task_id = ?3",
+            params![i as i32, item_id, req.task_id],
+        )?;
+        if affected == 0 {
+            return Err(AppError::NotFound(format!("清单项 ID {} 不属于任务 {}", item_id, req.task_id)));
+        }
+    }
+    tx.commit()?;
+
+    renumber_task_items(conn, req.task_id)?;
+    get_task_detail(conn, req.task_id)
+}
+
+/// 获取清单项所属任务 ID
+fn get_item_task_id(conn: &Connection, item_id: i64) -> AppResult<i64> {
+    conn.query_row(
+        "SELECT task_id FROM task_items WHERE id = ?1",
+        params![item_id],
+        |row| row.get(0),
+    ).map_err(|_| AppError::NotFound(format!("清单项 ID {} 不存在", item_id)))
+}
+
+/// 按当前顺序重新编号清单项
+fn renumber_task_items(conn: &Connection, task_id: i64) -> AppResult<()> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM task_items WHERE task_id = ?1 ORDER BY sort_order ASC, id ASC"
+    )?;
+    let ids: Vec<i64> = stmt.query_map(params![task_id], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (i, id) in ids.iter().enumerate() {
+        conn.execute("UPDATE task_items SET sort_order = ?1 WHERE id = ?2", params![i as i32, id])?;
+    }
+    Ok(())
+}
+
+/// 根据清单完成情况同步开启了自动完成的任务
+///
+/// 全部完成时完成任务（重复任务则完成本次并进入下一次），有未完成项时重新打开。
+fn sync_auto_complete(conn: &Connection, task: &Task) -> AppResult<()> {
+    let Some(id) = task.id else { return Ok(()) };
+    if !task.auto_complete {
+        return Ok(());
+    }
+
+    let (total, done): (i32, i32) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(completed), 0) FROM task_items WHERE task_id = ?1",
+        params![id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let all_done = total > 0 && done == total;
+
+    if all_done && !task.completed {
+        if task.is_repeating() {
+            complete_occurrence(conn, task, chrono::Local::now().date_naive())?;
+        } else {
+            set_completed(conn, task, true)?;
+        }
+    } else if !all_done && task.completed {
+        set_completed(conn, task, false)?;
+    }
+    Ok(())
+}
+
+/// 获取任务的提醒设置
+pub fn get_task_reminders(conn: &Connection, task_id: i64) -> AppResult<Vec<TaskReminder>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, task_id, kind, offset_minutes, at_time
+         FROM task_reminders WHERE task_id = ?1 ORDER BY id ASC"
+    )?;
+
+    let reminders = stmt.query_map(params![task_id], |row| {
+        Ok(TaskReminder {
+            id: Some(row.get(0)?),
+            task_id: row.get(1)?,
+            kind: ReminderKind::from_str(&row.get::<_, String>(2)?),
+            offset_minutes: row.get(3)?,
+            at_time: row.get(4)?,
+        })
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    Ok(reminders)
+}
+
+/// 整体替换任务的提醒设置
+pub fn set_task_reminders(conn: &Connection, req: &SetTaskRemindersRequest) -> AppResult<Vec<TaskReminder>> {
+    get_task_by_id(conn, req.task_id)?;
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM task_reminders WHERE task_id = ?1", params![req.task_id])?;
+    for item in &req.reminders {
+        let (offset, at_time) = match item.kind {
+            ReminderKind::Before => {
+                let offset = item.offset_minutes.unwrap_or(0);
+                if offset < 0 {
+                    return Err(AppError::Business("提前提醒的分钟数不能为负数".to_string()));
+                }
+                (Some(offset), None)
+            }
+            ReminderKind::OnDay => {
+                let at_time = validate_time(item.at_time.as_deref())?
+                    .ok_or_else(|| AppError::Business("当天提醒需要指定时间".to_string()))?;
+                (None, Some(at_time))
+            }
+        };
+        tx.execute(
+            "INSERT INTO task_reminders (task_id, kind, offset_minutes, at_time) VALUES (?1, ?2, ?3, ?4)",
+            params![req.task_id, item.kind.as_str(), offset, at_time],
+        )?;
+    }
+    tx.commit()?;
+
+    get_task_reminders(conn, req.task_id)
+}
+
+/// 取出当前应发送的提醒，并记录为已发送
+///
+/// 只处理未完成、有截止日期且不在已归档列表中的任务。触发时刻已过但超出补发窗口的提醒直接标记为已发送，
+/// 避免应用长时间关闭后重新打开时一次弹出大量过期通知。
+pub fn take_due_reminders(conn: &Connection, now: NaiveDateTime) -> AppResult<Vec<DueReminder>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT r.id, r.kind, r.offset_minutes, r.at_time, r.last_fired_at,
+                t.id, t.title, t.due_date, t.due_time
+         FROM task_reminders r
+         INNER JOIN tasks t ON t.id = r.task_id
+         WHERE t.completed = 0 AND t.due_date IS NOT NULL AND {}",
+        ACTIVE_LIST_FILTER
+    ))?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            ReminderKind::from_str(&row.get::<_, String>(1)?),
+            row.get::<_, Option<i32>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, i64>(5)?,
+            row.get::<_, String>(6)?,
+            row.get::<_, String>(7)?,
+            row.get::<_, Option<String>>(8)?,
+        ))
+    })?.collect::<Result<Vec<_>, _>>()?;
+
+    let grace = chrono::Duration::minutes(MISSED_REMINDER_GRACE_MINUTES);
+    let mut due = Vec::new();
+    for (reminder_id, kind, offset, at_time, last_fired, task_id, title, due_date, due_time) in rows {
+        let Ok(due_date) = NaiveDate::parse_from_str(&due_date, "%Y-%m-%d") else { continue };
+
+        let trigger = match kind {
+            ReminderKind::Before => {
+                let time = due_time.as_deref().or(Some(DEFAULT_DUE_TIME)).and_then(parse_time);
+                time.map(|t| due_date.and_time(t) - chrono::Duration::minutes(offset.unwrap_or(0) as i64))
+            }
+            ReminderKind::OnDay => at_time.as_deref().and_then(parse_time).map(|t| due_date.and_time(t)),
+        };
+        let Some(trigger) = trigger else { continue };
+        let trigger_str = trigger.format("%Y-%m-%d %H:%M").to_string();
+
+        if trigger > now || last_fired.as_deref() == Some(trigger_str.as_str()) {
+            continue;
+        }
+
+        conn.execute(
+            "UPDATE task_reminders SET last_fired_at = ?1 WHERE id = ?2",
+            params![trigger_str, reminder_id],
+        )?;
+        if now - trigger <= grace {
+            due.push(DueReminder { reminder_id, task_id, task_title: title, due_date, due_time });
+        }
+    }
+
+    Ok(due)
+}
+
+/// 解析 HH:MM 格式的时间
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").ok()
+}
+
+/// 校验 HH:MM 时间，返回规范化后的字符串（空字符串视为清除）
+fn validate_time(time: Option<&str>) -> AppResult<Option<String>> {
+    match time.map(str::trim) {
+        Some(t) if !t.is_empty() => parse_time(t)
+            .map(|t| Some(t.format("%H:%M").to_string()))
+            .ok_or_else(|| AppError::Business(format!("时间格式无效（应为 HH:MM）: {}", t))),
+        _ => Ok(None),
+    }
+}
+
+/// 校验 RRULE，返回规范化后的字符串（空字符串视为清除）
+fn validate_rrule(rule: Option<&str>) -> AppResult<Option<String>> {
+    match rule.map(recurrence::normalize) {
+        Some(r) if !r.is_empty() => {
+            RRule::parse(&r)?;
+            Ok(Some(r))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// 校验并拼接 EXDATE 列表（存储为逗号分隔的 YYYY-MM-DD）
+fn join_exdates(items: &[String]) -> AppResult<Option<String>> {
+    if items.is_empty() {
+        return Ok(None);
+    }
+    let dates = recurrence::parse_exdates(items)?;
+    Ok(Some(dates.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(",")))
+}
+
+fn split_exdates(raw: Option<String>) -> Vec<String> {
+    raw.map(|s| s.split(',').filter(|d| !d.is_empty()).map(|d| d.to_string()).collect())
+        .unwrap_or_default()
+}
+
 /// 批量创建任务
 pub fn batch_create_tasks(conn: &Connection, tasks: &[CreateTaskRequest]) -> AppResult<Vec<Task>> {
     let mut created = Vec::new();
@@ -150,3 +891,153 @@ pub fn batch_create_tasks(conn: &Connection, tasks: &[CreateTaskRequest]) -> App
     }
     Ok(created)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::init_database;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        conn
+    }
+
+    fn create(conn: &Connection, json: &str) -> Task {
+        create_task(conn, &serde_json::from_str(json).unwrap()).unwrap()
+    }
+
+    fn complete(conn: &Connection, task: &Task, today: &str) -> Task {
+        complete_occurrence(conn, task, d(today)).unwrap()
+    }
+
+    #[test]
+    fn monthly_clamps_to_month_end_and_returns_to_anchor_day() {
+        let anchor = d("2026-01-31");
+        let feb = next_due_date(&RepeatType::Monthly, anchor, anchor, anchor);
+        assert_eq!(feb, d("2026-02-28"));
+        let mar = next_due_date(&RepeatType::Monthly, anchor, feb, feb);
+        assert_eq!(mar, d("2026-03-31"));
+        let apr = next_due_date(&RepeatType::Monthly, anchor, mar, mar);
+        assert_eq!(apr, d("2026-04-30"));
+    }
+
+    #[test]
+    fn monthly_uses_leap_day_in_leap_year() {
+        let anchor = d("2028-01-31");
+        assert_eq!(next_due_date(&RepeatType::Monthly, anchor, anchor, anchor), d("2028-02-29"));
+    }
+
+    #[test]
+    fn overdue_task_jumps_to_today_or_later() {
+        let anchor = d("2026-01-01");
+        assert_eq!(next_due_date(&RepeatType::Weekly, anchor, anchor, d("2026-03-04")), d("2026-03-05"));
+        assert_eq!(next_due_date(&RepeatType::Daily, anchor, anchor, d("2026-03-04")), d("2026-03-04"));
+    }
+
+    #[test]
+    fn completing_monthly_task_keeps_anchor() {
+        let conn = setup();
+        let task = create(&conn, r#"{"title":"月报","due_date":"2026-01-31","repeat":"monthly"}"#);
+
+        let task = complete(&conn, &task, "2026-01-31");
+        assert_eq!(task.due_date, Some(d("2026-02-28")));
+        let task = complete(&conn, &task, "2026-02-28");
+        assert_eq!(task.due_date, Some(d("2026-03-31")));
+        assert!(!task.completed);
+        assert_eq!(get_task_completions(&conn, task.id.unwrap()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn yearly_rrule_on_leap_day() {
+        let conn = setup();
+        let task = create(&conn, r#"{"title":"纪念日","due_date":"2024-02-29","rrule":"FREQ=YEARLY"}"#);
+        let task = complete(&conn, &task, "2024-02-29");
+        assert_eq!(task.due_date, Some(d("2028-02-29")));
+    }
+
+    #[test]
+    fn exhausted_count_marks_task_completed() {
+        let conn = setup();
+        let task = create(&conn, r#"{"title":"打卡","due_date":"2026-01-01","rrule":"FREQ=DAILY;COUNT=2"}"#);
+
+        let task = complete(&conn, &task, "2026-01-01");
+        assert_eq!(task.due_date, Some(d("2026-01-02")));
+        assert!(!task.completed);
+        let task = complete(&conn, &task, "2026-01-02");
+        assert!(task.completed);
+        assert_eq!(task.due_date, Some(d("2026-01-02")));
+    }
+
+    #[test]
+    fn reminders_skip_tasks_in_archived_lists() {
+        let conn = setup();
+        let list = task_list_dao::create_task_list(&conn, &serde_json::from_str(r#"{"name":"归档"}"#).unwrap()).unwrap();
+        let archived = create(&conn, &format!(r#"{{"title":"a","due_date":"2026-01-01","due_time":"10:00","list_id":{}}}"#, list.id.unwrap()));
+        let active = create(&conn, r#"{"title":"b","due_date":"2026-01-01","due_time":"10:00"}"#);
+        for task in [&archived, &active] {
+            let req: SetTaskRemindersRequest = serde_json::from_value(serde_json::json!({
+                "task_id": task.id, "reminders": [{ "kind": "before", "offset_minutes": 0 }],
+            })).unwrap();
+            set_task_reminders(&conn, &req).unwrap();
+        }
+        let req: crate::models::task_list::UpdateTaskListRequest =
+            serde_json::from_value(serde_json::json!({ "id": list.id.unwrap(), "archived": true })).unwrap();
+        task_list_dao::update_task_list(&conn, &req).unwrap();
+
+        let now = NaiveDateTime::parse_from_str("2026-01-01 10:00:30", "%Y-%m-%d %H:%M:%S").unwrap();
+        let due = take_due_reminders(&conn, now).unwrap();
+        assert_eq!(due.iter().map(|r| r.task_title.as_str()).collect::<Vec<_>>(), vec!["b"]);
+    }
+
+    #[test]
+    fn unchanged_rule_does_not_restart_count() {
+        let conn = setup();
+        let task = create(&conn, r#"{"title":"打卡","due_date":"2026-01-01","rrule":"FREQ=DAILY;COUNT=2"}"#);
+        let task = complete(&conn, &task, "2026-01-01");
+        let id = task.id.unwrap();
+
+        // 重新提交相同的规则和截止日期，不应重置锚点
+        let req: UpdateTaskRequest = serde_json::from_value(serde_json::json!({
+            "id": id, "title": "打卡", "due_date": "2026-01-02", "rrule": "rrule:freq=daily;count=2",
+        })).unwrap();
+        let task = update_task(&conn, &req).unwrap();
+        let task = complete(&conn, &task, "2026-01-02");
+        assert!(task.completed);
+
+        // 改了截止日期后从新日期重新计数
+        let req: UpdateTaskRequest = serde_json::from_value(serde_json::json!({
+            "id": id, "due_date": "2026-02-01", "completed": false,
+        })).unwrap();
+        let task = update_task(&conn, &req).unwrap();
+        let task = complete(&conn, &task, "2026-02-01");
+        assert_eq!(task.due_date, Some(d("2026-02-02")));
+    }
+
+    #[test]
+    fn invalid_update_leaves_task_unchanged() {
+        let conn = setup();
+        let task = create(&conn, r#"{"title":"周报","due_date":"2026-01-05","rrule":"FREQ=WEEKLY"}"#);
+        let id = task.id.unwrap();
+
+        for invalid in [
+            serde_json::json!({"id": id, "title": "改名", "due_date": "2026-01-06", "rrule": "FREQ=SOMETIMES"}),
+            serde_json::json!({"id": id, "title": "改名", "due_date": "2026-01-06", "due_time": "25:00"}),
+            serde_json::json!({"id": id, "title": "改名", "due_date": "2026-01-06", "exdates": ["not-a-date"]}),
+        ] {
+            let req: UpdateTaskRequest = serde_json::from_value(invalid).unwrap();
+            assert!(update_task(&conn, &req).is_err());
+        }
+
+        let unchanged = get_task_by_id(&conn, id).unwrap();
+        assert_eq!(unchanged.title, "周报");
+        assert_eq!(unchanged.due_date, Some(d("2026-01-05")));
+        assert_eq!(unchanged.rrule.as_deref(), Some("FREQ=WEEKLY"));
+        assert!(task_event_dao::get_task_events(&conn, id).unwrap()
+            .iter().all(|e| e.event_type != TaskEventType::Rescheduled));
+    }
+}
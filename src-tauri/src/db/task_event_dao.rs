@@ -0,0 +1,126 @@
+//! 任务事件与任务统计数据库操作
+
+use rusqlite::{params, Connection};
+use chrono::{Duration, NaiveDate};
+use crate::errors::{AppError, AppResult};
+use crate::models::task_event::*;
+
+/// 有效的完成事件：之后没有被重新打开（中间没有再次完成）的完成
+///
+/// 列：task_id, occurred_at, due_date
+pub const EFFECTIVE_COMPLETIONS: &str =
+    "SELECT c.task_id, c.occurred_at, c.due_date FROM task_events c
+     WHERE c.event_type = 'completed'
+       AND NOT EXISTS (
+           SELECT 1 FROM task_events r
+           WHERE r.task_id = c.task_id AND r.event_type = 'reopened' AND r.id > c.id
+             AND NOT EXISTS (
+                 SELECT 1 FROM task_events n
+                 WHERE n.task_id = c.task_id AND n.event_type = 'completed' AND n.id > c.id AND n.id < r.id
+             )
+       )";
+
+/// 统计缺省的天数
+const DEFAULT_STATS_DAYS: i64 = 30;
+
+/// 记录任务事件
+pub fn record_event(
+    conn: &Connection,
+    task_id: i64,
+    event_type: TaskEventType,
+    due_date: Option<&str>,
+    previous_due_date: Option<&str>,
+) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO task_events (task_id, event_type, due_date, previous_due_date) VALUES (?1, ?2, ?3, ?4)",
+        params![task_id, event_type.as_str(), due_date, previous_due_date],
+    )?;
+    Ok(())
+}
+
+/// 获取任务的事件历史（按时间升序）
+pub fn get_task_events(conn: &Connection, task_id: i64) -> AppResult<Vec<TaskEvent>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, task_id, event_type, occurred_at, due_date, previous_due_date
+         FROM task_events WHERE task_id = ?1
+         ORDER BY id ASC"
+    )?;
+    let events = stmt.query_map(params![task_id], |row| {
+        Ok(TaskEvent {
+            id: row.get(0)?,
+            task_id: row.get(1)?,
+            event_type: TaskEventType::from_str(&row.get::<_, String>(2)?),
+            occurred_at: row.get(3)?,
+            due_date: row.get(4)?,
+            previous_due_date: row.get(5)?,
+        })
+    })?.collect::<Result<Vec<_>, _>>()?;
+    Ok(events)
+}
+
+/// 统计日期范围内的任务完成情况
+pub fn get_task_stats(conn: &Connection, req: &TaskStatsRequest) -> AppResult<TaskStats> {
+    let parse = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|e| AppError::Business(format!("日期格式无效: {}", e)));
+    let end = match req.end_date.as_deref() {
+        Some(s) => parse(s)?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let start = match req.start_date.as_deref() {
+        Some(s) => parse(s)?,
+        None => end - Duration::days(DEFAULT_STATS_DAYS - 1),
+    };
+    if end < start {
+        return Err(AppError::Business("结束日期不能早于开始日期".to_string()));
+    }
+    let (start_str, end_str) = (start.format("%Y-%m-%d").to_string(), end.format("%Y-%m-%d").to_string());
+
+    let created: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM task_events WHERE event_type = 'created' AND date(occurred_at) BETWEEN ?1 AND ?2",
+        params![start_str, end_str],
+        |row| row.get(0),
+    )?;
+
+    let (completed, on_time, overdue, lead_time): (i64, i64, i64, Option<f64>) = conn.query_row(
+        &format!(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(c.due_date IS NOT NULL AND date(c.occurred_at) <= c.due_date), 0),
+                    COALESCE(SUM(c.due_date IS NOT NULL AND date(c.occurred_at) > c.due_date), 0),
+                    AVG(CASE WHEN t.repeat IS NULL AND t.rrule IS NULL
+                             THEN (julianday(c.occurred_at) - julianday(t.created_at)) * 24 END)
+             FROM ({}) c
+             INNER JOIN tasks t ON t.id = c.task_id
+             WHERE date(c.occurred_at) BETWEEN ?1 AND ?2",
+            EFFECTIVE_COMPLETIONS
+        ),
+        params![start_str, end_str],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    )?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT date(occurred_at), COUNT(*) FROM ({})
+         WHERE date(occurred_at) BETWEEN ?1 AND ?2
+         GROUP BY 1",
+        EFFECTIVE_COMPLETIONS
+    ))?;
+    let counts: std::collections::HashMap<String, i64> = stmt.query_map(params![start_str, end_str], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })?.collect::<Result<_, _>>()?;
+    let per_day = start.iter_days().take_while(|d| *d <= end).map(|d| {
+        let date = d.format("%Y-%m-%d").to_string();
+        TaskDailyCount { count: counts.get(&date).copied().unwrap_or(0), date }
+    }).collect();
+
+    Ok(TaskStats {
+        start_date: start_str,
+        end_date: end_str,
+        created,
+        completed,
+        completed_on_time: on_time,
+        completed_overdue: overdue,
+        completed_without_due: completed - on_time - overdue,
+        on_time_rate: (on_time + overdue > 0).then(|| on_time as f64 / (on_time + overdue) as f64),
+        avg_lead_time_hours: lead_time,
+        per_day,
+    })
+}
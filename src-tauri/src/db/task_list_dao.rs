@@ -0,0 +1,165 @@
+//! 任务列表（项目）数据库操作
+
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::db::name_rule::NameRule;
+use crate::errors::{AppError, AppResult};
+use crate::models::task_list::*;
+
+/// 列表名称规则
+const LIST_NAME: NameRule = NameRule { table: "task_lists", label: "列表" };
+
+/// 任务列表查询的列（顺序与 [`map_list_row`] 对应）
+const LIST_COLUMNS: &str =
+    "l.id, l.name, l.color, l.sort_order, l.archived, l.created_at, l.updated_at,
+     (SELECT COUNT(*) FROM tasks t WHERE t.list_id = l.id),
+     (SELECT COUNT(*) FROM tasks t WHERE t.list_id = l.id AND t.completed = 0)";
+
+/// 获取任务列表（按排序序号），默认不含已归档的列表
+pub fn get_task_lists(conn: &Connection, include_archived: bool) -> AppResult<Vec<TaskList>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM task_lists l
+         WHERE ?1 = 1 OR l.archived = 0
+         ORDER BY l.archived ASC, l.sort_order ASC, l.id ASC",
+        LIST_COLUMNS
+    ))?;
+
+    let lists = stmt.query_map(params![include_archived as i32], map_list_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(lists)
+}
+
+/// 根据 ID 获取任务列表
+pub fn get_task_list_by_id(conn: &Connection, id: i64) -> AppResult<TaskList> {
+    conn.query_row(
+        &format!("SELECT {} FROM task_lists l WHERE l.id = ?1", LIST_COLUMNS),
+        params![id],
+        map_list_row,
+    ).map_err(|_| AppError::NotFound(format!("任务列表 ID {} 不存在", id)))
+}
+
+/// 创建任务列表（名称不区分大小写唯一，排在末尾）
+pub fn create_task_list(conn: &Connection, req: &CreateTaskListRequest) -> AppResult<TaskList> {
+    let name = LIST_NAME.validate(&req.name)?;
+    LIST_NAME.ensure_available(conn, &name, None)?;
+
+    conn.execute(
+        "INSERT INTO task_lists (name, color, sort_order)
+         VALUES (?1, ?2, (SELECT COALESCE(MAX(sort_order) + 1, 0) FROM task_lists))",
+        params![name, req.color],
+    )?;
+    get_task_list_by_id(conn, conn.last_insert_rowid())
+}
+
+/// 更新任务列表（名称、颜色、归档状态）
+pub fn update_task_list(conn: &Connection, req: &UpdateTaskListRequest) -> AppResult<TaskList> {
+    get_task_list_by_id(conn, req.id)?;
+
+    if let Some(ref name) = req.name {
+        let name = LIST_NAME.validate(name)?;
+        LIST_NAME.ensure_available(conn, &name, Some(req.id))?;
+        conn.execute("UPDATE task_lists SET name = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![name, req.id])?;
+    }
+    if let Some(ref color) = req.color {
+        conn.execute("UPDATE task_lists SET color = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![color, req.id])?;
+    }
+    if let Some(archived) = req.archived {
+        conn.execute("UPDATE task_lists SET archived = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2", params![archived as i32, req.id])?;
+    }
+
+    get_task_list_by_id(conn, req.id)
+}
+
+/// 删除任务列表（列表中的任务随外键移回收件箱，不会被删除）
+pub fn delete_task_list(conn: &Connection, id: i64) -> AppResult<()> {
+    let affected = conn.execute("DELETE FROM task_lists WHERE id = ?1", params![id])?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("任务列表 ID {} 不存在", id)));
+    }
+    renumber_task_lists(conn)?;
+    Ok(())
+}
+
+/// 按给定顺序重排任务列表
+pub fn reorder_task_lists(conn: &Connection, req: &ReorderTaskListsRequest) -> AppResult<Vec<TaskList>> {
+    let tx = conn.unchecked_transaction()?;
+    // 先整体后移，请求中未包含的列表自然排到末尾
+    tx.execute(
+        "UPDATE task_lists SET sort_order = sort_order + ?1",
+        params![req.list_ids.len() as i32],
+    )?;
+    for (i, list_id) in req.list_ids.iter().enumerate() {
+        let affected = tx.execute(
+            "UPDATE task_lists SET sort_order = ?1 WHERE id = ?2",
+            params![i as i32, list_id],
+        )?;
+        if affected == 0 {
+            return Err(AppError::NotFound(format!("任务列表 ID {} 不存在", list_id)));
+        }
+    }
+    tx.commit()?;
+
+    renumber_task_lists(conn)?;
+    get_task_lists(conn, true)
+}
+
+/// 将任务移动到指定列表（`list_id` 为空时移回收件箱）
+pub fn move_tasks(conn: &Connection, req: &MoveTasksRequest) -> AppResult<()> {
+    if let Some(list_id) = req.list_id {
+        get_task_list_by_id(conn, list_id)?;
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    for task_id in &req.task_ids {
+        let affected = tx.execute(
+            "UPDATE tasks SET list_id = ?1, updated_at = datetime('now', 'localtime') WHERE id = ?2",
+            params![req.list_id, task_id],
+        )?;
+        if affected == 0 {
+            return Err(AppError::NotFound(format!("任务 ID {} 不存在", task_id)));
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// 按名称查找任务列表，不存在时创建（用于批量导入）
+pub fn find_or_create_task_list(conn: &Connection, name: &str) -> AppResult<i64> {
+    let name = LIST_NAME.validate(name)?;
+    let existing: Option<i64> = conn.query_row(
+        "SELECT id FROM task_lists WHERE name = ?1 COLLATE NOCASE",
+        params![name],
+        |row| row.get(0),
+    ).optional()?;
+
+    match existing {
+        Some(id) => Ok(id),
+        None => create_task_list(conn, &CreateTaskListRequest { name, color: None })
+            .map(|list| list.id.unwrap_or_default()),
+    }
+}
+
+fn map_list_row(row: &rusqlite::Row) -> rusqlite::Result<TaskList> {
+    Ok(TaskList {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        color: row.get(2)?,
+        sort_order: row.get(3)?,
+        archived: row.get::<_, i32>(4)? != 0,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+        task_count: row.get(7)?,
+        open_task_count: row.get(8)?,
+    })
+}
+
+/// 按当前顺序重新编号任务列表
+fn renumber_task_lists(conn: &Connection) -> AppResult<()> {
+    let mut stmt = conn.prepare("SELECT id FROM task_lists ORDER BY sort_order ASC, id ASC")?;
+    let ids: Vec<i64> = stmt.query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (i, id) in ids.iter().enumerate() {
+        conn.execute("UPDATE task_lists SET sort_order = ?1 WHERE id = ?2", params![i as i32, id])?;
+    }
+    Ok(())
+}
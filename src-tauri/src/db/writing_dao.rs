@@ -1,57 +1,195 @@
 //! 写作记录数据库操作
 
 use rusqlite::{params, Connection};
+use chrono::NaiveDate;
 use crate::errors::{AppError, AppResult};
 use crate::models::writing::*;
+use crate::models::streak::StreakSummary;
+use crate::db::{draft_dao, goal_dao, revision_dao, settings_dao, tag_dao};
+use crate::streak::{self, StreakRules};
+use crate::text_metrics;
 
-/// 保存写作记录
+/// 写作记录查询的列（表别名为 `w`，顺序与 [`map_writing_row`] 对应）
+const WRITING_COLUMNS: &str =
+    "w.id, w.plan_day_id, w.title, w.content, w.word_count, w.duration_seconds, w.written_date, w.created_at,
+     (SELECT GROUP_CONCAT(wt.tag_id) FROM writing_tags wt WHERE wt.writing_id = w.id),
+     w.updated_at, w.cjk_chars, w.latin_words, w.punctuation_count, w.paragraph_count";
+
+/// 将查询结果行映射为写作记录
+fn map_writing_row(row: &rusqlite::Row) -> rusqlite::Result<Writing> {
+    Ok(Writing {
+        id: Some(row.get(0)?),
+        plan_day_id: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get(3)?,
+        word_count: row.get(4)?,
+        duration_seconds: row.get(5)?,
+        written_date: row.get(6)?,
+        created_at: row.get(7)?,
+        tag_ids: tag_dao::split_tag_ids(row.get(8)?),
+        updated_at: row.get(9)?,
+        metrics: TextMetrics {
+            word_count: row.get(4)?,
+            cjk_chars: row.get(10)?,
+            latin_words: row.get(11)?,
+            punctuation: row.get(12)?,
+            paragraphs: row.get(13)?,
+        },
+    })
+}
+
+/// 保存写作记录（同时记录首个修订版本并清除对应的草稿，在同一事务中完成）
+///
+/// 字数等统计由 [`text_metrics`] 根据正文计算，忽略请求中的 `word_count`。
 pub fn save_writing(conn: &Connection, req: &SaveWritingRequest) -> AppResult<Writing> {
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let metrics = text_metrics::analyze_html(&req.content);
 
-    conn.execute(
-        "INSERT INTO writings (plan_day_id, title, content, word_count, duration_seconds, written_date)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO writings (plan_day_id, title, content, plain_text, word_count, duration_seconds, written_date, updated_at,
+                               cjk_chars, latin_words, punctuation_count, paragraph_count)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now', 'localtime'), ?8, ?9, ?10, ?11)",
         params![
             req.plan_day_id,
             req.title,
             req.content,
-            req.word_count,
+            text_metrics::html_to_text(&req.content),
+            metrics.word_count,
             req.duration_seconds,
             today,
+            metrics.cjk_chars,
+            metrics.latin_words,
+            metrics.punctuation,
+            metrics.paragraphs,
         ],
     )?;
 
-    let id = conn.last_insert_rowid();
+    let id = tx.last_insert_rowid();
+    revision_dao::record_revision(&tx, id)?;
+    draft_dao::clear_draft(&tx, req.plan_day_id)?;
+    tx.commit()?;
+
     get_writing_by_id(conn, id)
 }
 
+/// 更新写作记录（标题、正文、计划日关联）
+///
+/// 标题或正文有变化时记录一个新的修订版本。
+///
+/// 统计、热力图和计划完成天数都是查询时从 writings 汇总的，更新后自然保持一致。
+pub fn update_writing(conn: &Connection, req: &UpdateWritingRequest) -> AppResult<Writing> {
+    get_writing_by_id(conn, req.id)?;
+    if let Some(plan_day_id) = req.plan_day_id {
+        ensure_plan_day_exists(conn, plan_day_id)?;
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    if let Some(ref title) = req.title {
+        tx.execute("UPDATE writings SET title = ?1 WHERE id = ?2", params![title, req.id])?;
+    }
+    if let Some(ref content) = req.content {
+        tx.execute(
+            "UPDATE writings SET content = ?1, plain_text = ?2 WHERE id = ?3",
+            params![content, text_metrics::html_to_text(content), req.id],
+        )?;
+        save_metrics(&tx, req.id, &text_metrics::analyze_html(content))?;
+    }
+    if req.clear_plan_day {
+        tx.execute("UPDATE writings SET plan_day_id = NULL WHERE id = ?1", params![req.id])?;
+    } else if let Some(plan_day_id) = req.plan_day_id {
+        tx.execute("UPDATE writings SET plan_day_id = ?1 WHERE id = ?2", params![plan_day_id, req.id])?;
+    }
+    tx.execute("UPDATE writings SET updated_at = datetime('now', 'localtime') WHERE id = ?1", params![req.id])?;
+    revision_dao::record_revision(&tx, req.id)?;
+    tx.commit()?;
+
+    get_writing_by_id(conn, req.id)
+}
+
+/// 删除写作记录（标签关联随外键级联删除，关联的计划日恢复为未完成）
+pub fn delete_writing(conn: &Connection, id: i64) -> AppResult<()> {
+    let affected = conn.execute("DELETE FROM writings WHERE id = ?1", params![id])?;
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("写作记录 ID {} 不存在", id)));
+    }
+    Ok(())
+}
+
+/// 按当前统计规则重新计算所有写作记录（及其历史版本）的字数，并刷新搜索用的纯文本
+pub fn recompute_metrics(conn: &Connection) -> AppResult<RecomputeMetricsResult> {
+    let tx = conn.unchecked_transaction()?;
+    let mut result = RecomputeMetricsResult::default();
+
+    let rows: Vec<(i64, String, i32)> = {
+        let mut stmt = tx.prepare("SELECT id, content, word_count FROM writings")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+    for (id, content, word_count) in rows {
+        let metrics = text_metrics::analyze_html(&content);
+        save_metrics(&tx, id, &metrics)?;
+        tx.execute(
+            "UPDATE writings SET plain_text = ?1 WHERE id = ?2",
+            params![text_metrics::html_to_text(&content), id],
+        )?;
+        result.scanned += 1;
+        if metrics.word_count != word_count {
+            result.changed += 1;
+        }
+    }
+
+    let revisions: Vec<(i64, String)> = {
+        let mut stmt = tx.prepare("SELECT id, content FROM writing_revisions")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+    for (id, content) in revisions {
+        tx.execute(
+            "UPDATE writing_revisions SET word_count = ?1 WHERE id = ?2",
+            params![text_metrics::analyze_html(&content).word_count, id],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(result)
+}
+
+/// 写入写作记录的字数统计
+fn save_metrics(conn: &Connection, id: i64, metrics: &TextMetrics) -> AppResult<()> {
+    conn.execute(
+        "UPDATE writings SET word_count = ?1, cjk_chars = ?2, latin_words = ?3, punctuation_count = ?4, paragraph_count = ?5
+         WHERE id = ?6",
+        params![metrics.word_count, metrics.cjk_chars, metrics.latin_words, metrics.punctuation, metrics.paragraphs, id],
+    )?;
+    Ok(())
+}
+
+fn ensure_plan_day_exists(conn: &Connection, plan_day_id: i64) -> AppResult<()> {
+    let exists: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM plan_days WHERE id = ?1",
+        params![plan_day_id],
+        |row| row.get(0),
+    )?;
+    if exists == 0 {
+        return Err(AppError::NotFound(format!("计划日条目 ID {} 不存在", plan_day_id)));
+    }
+    Ok(())
+}
+
 /// 根据 ID 获取写作记录
 pub fn get_writing_by_id(conn: &Connection, id: i64) -> AppResult<Writing> {
-    let mut stmt = conn.prepare(
-        "SELECT id, plan_day_id, title, content, word_count, duration_seconds, written_date, created_at
-         FROM writings WHERE id = ?1"
-    )?;
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM writings w WHERE w.id = ?1", WRITING_COLUMNS))?;
 
-    stmt.query_row(params![id], |row| {
-        Ok(Writing {
-            id: Some(row.get(0)?),
-            plan_day_id: row.get(1)?,
-            title: row.get(2)?,
-            content: row.get(3)?,
-            word_count: row.get(4)?,
-            duration_seconds: row.get(5)?,
-            written_date: row.get(6)?,
-            created_at: row.get(7)?,
-        })
-    }).map_err(|_| AppError::NotFound(format!("写作记录 ID {} 不存在", id)))
+    stmt.query_row(params![id], map_writing_row)
+        .map_err(|_| AppError::NotFound(format!("写作记录 ID {} 不存在", id)))
 }
 
 /// 获取写作记录列表（支持过滤）
 pub fn get_writings(conn: &Connection, filter: &WritingFilter) -> AppResult<Vec<Writing>> {
-    let mut sql = String::from(
-        "SELECT w.id, w.plan_day_id, w.title, w.content, w.word_count, w.duration_seconds, w.written_date, w.created_at
-         FROM writings w"
-    );
+    let mut sql = format!("SELECT {} FROM writings w", WRITING_COLUMNS);
     let mut conditions = Vec::new();
     let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
@@ -69,6 +207,20 @@ pub fn get_writings(conn: &Connection, filter: &WritingFilter) -> AppResult<Vec<
         conditions.push(format!("w.written_date <= ?{}", param_values.len() + 1));
         param_values.push(Box::new(end.clone()));
     }
+    if let Some(ref tag_ids) = filter.tag_ids {
+        if !tag_ids.is_empty() {
+            let placeholders: Vec<String> = tag_ids.iter().enumerate()
+                .map(|(i, _)| format!("?{}", param_values.len() + i + 1))
+                .collect();
+            conditions.push(format!(
+                "w.id IN (SELECT writing_id FROM writing_tags WHERE tag_id IN ({}))",
+                placeholders.join(", ")
+            ));
+            for id in tag_ids {
+                param_values.push(Box::new(*id));
+            }
+        }
+    }
 
     if !conditions.is_empty() {
         sql.push_str(" WHERE ");
@@ -79,24 +231,13 @@ pub fn get_writings(conn: &Connection, filter: &WritingFilter) -> AppResult<Vec<
     let mut stmt = conn.prepare(&sql)?;
     let params_refs: Vec<&dyn rusqlite::types::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
 
-    let writings = stmt.query_map(params_refs.as_slice(), |row| {
-        Ok(Writing {
-            id: Some(row.get(0)?),
-            plan_day_id: row.get(1)?,
-            title: row.get(2)?,
-            content: row.get(3)?,
-            word_count: row.get(4)?,
-            duration_seconds: row.get(5)?,
-            written_date: row.get(6)?,
-            created_at: row.get(7)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+    let writings = stmt.query_map(params_refs.as_slice(), map_writing_row)?.collect::<Result<Vec<_>, _>>()?;
 
     Ok(writings)
 }
 
-/// 获取写作统计
-pub fn get_writing_stats(conn: &Connection) -> AppResult<WritingStats> {
+/// 获取写作统计（连续天数以 `today` 为今天计算）
+pub fn get_writing_stats(conn: &Connection, today: NaiveDate) -> AppResult<WritingStats> {
     let total_days: i32 = conn.query_row(
         "SELECT COUNT(DISTINCT written_date) FROM writings",
         [], |row| row.get(0),
@@ -121,98 +262,37 @@ pub fn get_writing_stats(conn: &Connection) -> AppResult<WritingStats> {
     let avg_duration = if total_sessions > 0 { total_duration as f64 / total_sessions as f64 } else { 0.0 };
 
     // 计算连续天数
-    let (current_streak, max_streak) = calculate_streaks(conn)?;
+    let streak_requires_goal = settings_dao::get_streak_settings(conn)?.require_daily_goal;
+    let summary = get_streak_summary(conn, today)?;
 
     Ok(WritingStats {
         total_days,
         total_words,
         total_duration,
-        current_streak,
-        max_streak,
+        current_streak: summary.current_streak,
+        max_streak: summary.max_streak,
+        longest_streak: summary.longest,
+        streak_requires_goal,
         avg_words_per_session: avg_words,
         avg_duration_per_session: avg_duration,
     })
 }
 
-/// 计算连续写作天数
-fn calculate_streaks(conn: &Connection) -> AppResult<(i32, i32)> {
-    let mut stmt = conn.prepare(
-        "SELECT DISTINCT written_date FROM writings ORDER BY written_date DESC"
-    )?;
-
-    let dates: Vec<String> = stmt.query_map([], |row| {
-        row.get::<_, String>(0)
-    })?.collect::<Result<Vec<_>, _>>()?;
-
-    if dates.is_empty() {
-        return Ok((0, 0));
-    }
-
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let mut current_streak = 0;
-    let mut max_streak = 0;
-    let mut streak = 0;
-
-    for (i, date_str) in dates.iter().enumerate() {
-        if i == 0 {
-            // 检查最近一次写作是否是今天或昨天
-            if date_str == &today
-                || date_str == &(chrono::Local::now() - chrono::Duration::days(1)).format("%Y-%m-%d").to_string()
-            {
-                streak = 1;
-            } else {
-                // 最近写作不是今天/昨天，当前连续中断
-                streak = 1; // 仍算一天
-                max_streak = 1;
-                // 继续扫描计算最大连续
-                current_streak = 0;
-            }
-        } else {
-            let prev = chrono::NaiveDate::parse_from_str(&dates[i - 1], "%Y-%m-%d");
-            let curr = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d");
-            if let (Ok(p), Ok(c)) = (prev, curr) {
-                if (p - c).num_days() == 1 {
-                    streak += 1;
-                } else {
-                    if current_streak == 0 && i <= dates.len() {
-                        current_streak = streak;
-                    }
-                    max_streak = max_streak.max(streak);
-                    streak = 1;
-                }
-            }
-        }
-    }
-
-    // 最终更新
-    if current_streak == 0 {
-        current_streak = streak;
-    }
-    max_streak = max_streak.max(streak);
-
-    Ok((current_streak, max_streak))
-}
-
-/// 获取热力图数据（最近一年）
-pub fn get_heatmap_data(conn: &Connection) -> AppResult<Vec<HeatmapEntry>> {
-    let one_year_ago = (chrono::Local::now() - chrono::Duration::days(365))
-        .format("%Y-%m-%d").to_string();
-
-    let mut stmt = conn.prepare(
-        "SELECT written_date, COALESCE(SUM(word_count), 0), COUNT(*)
-         FROM writings
-         WHERE written_date >= ?1
-         GROUP BY written_date
-         ORDER BY written_date ASC"
-    )?;
-
-    let entries = stmt.query_map(params![one_year_ago], |row| {
-        Ok(HeatmapEntry {
-            date: row.get(0)?,
-            word_count: row.get(1)?,
-            count: row.get(2)?,
-        })
-    })?.collect::<Result<Vec<_>, _>>()?;
+/// 计算连续写作统计（按设置决定是否只统计达成每日目标的日子，以及休息日 / 冻结日）
+pub fn get_streak_summary(conn: &Connection, today: NaiveDate) -> AppResult<StreakSummary> {
+    let settings = settings_dao::get_streak_settings(conn)?;
+    let dates: Vec<String> = if settings.require_daily_goal {
+        goal_dao::get_daily_goal_met_dates(conn)?
+    } else {
+        let mut stmt = conn.prepare("SELECT DISTINCT written_date FROM writings")?;
+        let dates = stmt.query_map([], |row| {
+            row.get::<_, String>(0)
+        })?.collect::<Result<Vec<_>, _>>()?;
+        dates
+    };
 
-    Ok(entries)
+    let dates: Vec<NaiveDate> = dates.iter()
+        .filter_map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        .collect();
+    Ok(streak::summarize(&dates, today, &StreakRules::from_settings(&settings)))
 }
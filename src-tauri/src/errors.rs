@@ -31,6 +31,14 @@ pub enum AppError {
     /// Markdown 解析错误
     #[error("Markdown 解析错误: {0}")]
     MarkdownParse(String),
+
+    /// 数据库迁移错误
+    #[error("数据库迁移错误: {0}")]
+    Migration(String),
+
+    /// 数据库版本高于当前程序支持的版本（通常是降级安装导致）
+    #[error("数据库版本 v{found} 高于当前程序支持的 v{supported}，请升级 WriteDo 后再打开")]
+    DatabaseTooNew { found: i64, supported: i64 },
 }
 
 /// 实现 Serialize 以便 Tauri 可以将错误传递给前端
@@ -8,6 +8,12 @@ mod models;
 mod db;
 mod commands;
 mod plan_generator;
+mod recurrence;
+mod text_diff;
+mod text_metrics;
+mod streak;
+mod plan_schedule;
+mod scheduler;
 
 use std::sync::Mutex;
 use rusqlite::Connection;
@@ -17,6 +23,14 @@ use commands::task_cmd;
 use commands::plan_cmd;
 use commands::writing_cmd;
 use commands::import_cmd;
+use commands::settings_cmd;
+use commands::tag_cmd;
+use commands::task_list_cmd;
+use commands::search_cmd;
+use commands::draft_cmd;
+use commands::revision_cmd;
+use commands::goal_cmd;
+use commands::analytics_cmd;
 
 /// 初始化数据库连接
 fn init_db() -> Connection {
@@ -31,8 +45,9 @@ fn init_db() -> Connection {
     let conn = Connection::open(&db_path)
         .unwrap_or_else(|e| panic!("无法打开数据库 {:?}: {}", db_path, e));
 
-    // 初始化表结构
-    db::init::init_database(&conn).expect("数据库初始化失败");
+    // 初始化表结构（含版本迁移）
+    db::init::init_database(&conn)
+        .unwrap_or_else(|e| panic!("数据库初始化失败: {}", e));
 
     conn
 }
@@ -46,6 +61,8 @@ pub fn run() {
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        // 日志写到系统日志目录（开发时同时输出到终端），后台任务的错误可以事后排查
+        .plugin(tauri_plugin_log::Builder::new().level(log::LevelFilter::Info).build())
         .manage(Mutex::new(conn))
         .setup(|app| {
             // ── 系统托盘菜单 ──
@@ -92,16 +109,34 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            // ── 后台提醒调度 ──
+            scheduler::start(app.handle().clone());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             // 任务管理
             task_cmd::get_tasks,
             task_cmd::get_today_tasks,
+            task_cmd::query_tasks,
+            task_cmd::get_task_buckets,
+            task_cmd::roll_over_overdue_tasks,
             task_cmd::create_task,
             task_cmd::update_task,
             task_cmd::delete_task,
             task_cmd::toggle_task,
+            task_cmd::get_task_completions,
+            task_cmd::get_task_events,
+            task_cmd::get_task_stats,
+            task_cmd::preview_rrule,
+            task_cmd::get_task_reminders,
+            task_cmd::set_task_reminders,
+            task_cmd::get_task_detail,
+            task_cmd::add_task_item,
+            task_cmd::update_task_item,
+            task_cmd::toggle_task_item,
+            task_cmd::delete_task_item,
+            task_cmd::reorder_task_items,
             // 写作计划
             plan_cmd::get_plans,
             plan_cmd::get_plan_detail,
@@ -109,21 +144,70 @@ pub fn run() {
             plan_cmd::import_plan,
             plan_cmd::generate_plan,
             plan_cmd::update_plan,
+            plan_cmd::catch_up_plan,
             plan_cmd::update_plan_status,
+            plan_cmd::get_plan_events,
             plan_cmd::delete_plan,
             plan_cmd::update_plan_day,
             plan_cmd::delete_plan_day,
             plan_cmd::add_plan_day,
             // 写作记录
             writing_cmd::save_writing,
+            writing_cmd::update_writing,
+            writing_cmd::delete_writing,
+            writing_cmd::analyze_writing_text,
+            writing_cmd::recompute_writing_metrics,
             writing_cmd::get_writings,
             writing_cmd::get_writing_stats,
+            writing_cmd::get_streak_summary,
             writing_cmd::get_heatmap,
             writing_cmd::get_writing_detail,
+            // 修订历史
+            revision_cmd::get_writing_revisions,
+            revision_cmd::get_writing_revision,
+            revision_cmd::diff_writing_revisions,
+            revision_cmd::restore_writing_revision,
+            // 写作目标
+            goal_cmd::get_writing_goals,
+            goal_cmd::set_writing_goal,
+            goal_cmd::delete_writing_goal,
+            goal_cmd::get_goal_progress,
+            // 写作分析
+            analytics_cmd::get_writing_analytics,
+            // 写作草稿
+            draft_cmd::save_draft,
+            draft_cmd::get_drafts,
+            draft_cmd::get_draft,
+            draft_cmd::discard_draft,
             // 批量导入
             import_cmd::import_tasks_json,
             import_cmd::import_plan_json,
             import_cmd::import_plan_markdown,
+            // 任务列表
+            task_list_cmd::get_task_lists,
+            task_list_cmd::create_task_list,
+            task_list_cmd::update_task_list,
+            task_list_cmd::delete_task_list,
+            task_list_cmd::reorder_task_lists,
+            task_list_cmd::get_list_tasks,
+            task_list_cmd::move_tasks,
+            // 标签
+            tag_cmd::get_tags,
+            tag_cmd::create_tag,
+            tag_cmd::update_tag,
+            tag_cmd::delete_tag,
+            tag_cmd::set_task_tags,
+            tag_cmd::set_writing_tags,
+            tag_cmd::get_tag_stats,
+            // 全文搜索
+            search_cmd::search,
+            // 应用设置
+            settings_cmd::get_writing_reminder_settings,
+            settings_cmd::update_writing_reminder_settings,
+            settings_cmd::get_streak_settings,
+            settings_cmd::update_streak_settings,
+            settings_cmd::get_plan_completion_settings,
+            settings_cmd::update_plan_completion_settings,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,81 @@
+//! 写作分析数据模型
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 统计粒度（周从周一开始）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalyticsGranularity {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// 分析请求
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsRequest {
+    /// 开始日期（含，YYYY-MM-DD）
+    pub start_date: String,
+    /// 结束日期（含，YYYY-MM-DD）
+    pub end_date: String,
+    pub granularity: AnalyticsGranularity,
+}
+
+/// 一段时间内的写作汇总
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AnalyticsTotals {
+    pub words: i64,
+    /// 写作次数（写作记录条数）
+    pub sessions: i64,
+    pub duration_seconds: i64,
+    /// 每分钟字数（只统计有时长记录的写作，没有时为 0）
+    pub words_per_minute: f64,
+    /// 有写作的天数
+    pub active_days: i64,
+    /// 计划写作（关联了每日题目）的字数和次数
+    pub plan_words: i64,
+    pub plan_sessions: i64,
+    /// 自由写作的字数和次数
+    pub free_words: i64,
+    pub free_sessions: i64,
+}
+
+/// 单个统计区间
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsBucket {
+    /// 区间开始日期（含；首尾区间按请求范围截断）
+    pub start: NaiveDate,
+    /// 区间结束日期（含）
+    pub end: NaiveDate,
+    #[serde(flatten)]
+    pub totals: AnalyticsTotals,
+}
+
+/// 与上一周期相比的变化百分比（上一周期为 0 时为空）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AnalyticsChange {
+    pub words: Option<f64>,
+    pub sessions: Option<f64>,
+    pub duration_seconds: Option<f64>,
+    pub words_per_minute: Option<f64>,
+    pub active_days: Option<f64>,
+}
+
+/// 分析结果
+#[derive(Debug, Clone, Serialize)]
+pub struct WritingAnalytics {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub granularity: AnalyticsGranularity,
+    /// 按时间升序的区间，没有写作的区间也会返回（各项为 0）
+    pub buckets: Vec<AnalyticsBucket>,
+    /// 整个范围的汇总
+    pub totals: AnalyticsTotals,
+    /// 上一周期（紧挨在开始日期之前、天数相同的范围）
+    pub previous_start_date: NaiveDate,
+    pub previous_end_date: NaiveDate,
+    pub previous_totals: AnalyticsTotals,
+    pub change: AnalyticsChange,
+}
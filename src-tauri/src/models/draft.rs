@@ -0,0 +1,40 @@
+//! 写作草稿数据模型
+//!
+//! 编辑器定时自动保存草稿，正式保存写作记录后草稿即被清除。
+//! 启动时仍然存在的草稿说明上次写作没有正常保存，可以提示用户恢复。
+
+use serde::{Deserialize, Serialize};
+
+/// 写作草稿
+#[derive(Debug, Clone, Serialize)]
+pub struct Draft {
+    pub id: i64,
+    /// 关联的计划日条目ID（自由写作时为空）
+    pub plan_day_id: Option<i64>,
+    pub title: String,
+    pub content: String,
+    pub word_count: i32,
+    /// 已写作时长（秒）
+    pub duration_seconds: i32,
+    pub created_at: String,
+    pub updated_at: String,
+    /// 所属计划 ID
+    pub plan_id: Option<i64>,
+    /// 计划日条目标题（用于恢复提示）
+    pub plan_day_title: Option<String>,
+    /// 所属计划名称（用于恢复提示）
+    pub plan_name: Option<String>,
+}
+
+/// 自动保存草稿的请求
+#[derive(Debug, Deserialize)]
+pub struct SaveDraftRequest {
+    /// 关联的计划日条目ID（自由写作时为空）
+    #[serde(default)]
+    pub plan_day_id: Option<i64>,
+    #[serde(default)]
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub duration_seconds: i32,
+}
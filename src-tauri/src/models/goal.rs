@@ -0,0 +1,97 @@
+//! 写作目标数据模型
+//!
+//! 目标按「周期 + 指标」唯一，例如每天 500 字、每周 5 次、每月 20000 字。
+
+use serde::{Deserialize, Serialize};
+
+/// 目标周期（周从周一开始）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl GoalPeriod {
+    pub fn as_str(&self) -> &str {
+        match self {
+            GoalPeriod::Day => "day",
+            GoalPeriod::Week => "week",
+            GoalPeriod::Month => "month",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "week" => GoalPeriod::Week,
+            "month" => GoalPeriod::Month,
+            _ => GoalPeriod::Day,
+        }
+    }
+}
+
+/// 目标指标
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalMetric {
+    /// 字数
+    Words,
+    /// 写作次数（写作记录条数）
+    Sessions,
+    /// 写作时长（秒）
+    Duration,
+}
+
+impl GoalMetric {
+    pub fn as_str(&self) -> &str {
+        match self {
+            GoalMetric::Words => "words",
+            GoalMetric::Sessions => "sessions",
+            GoalMetric::Duration => "duration",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "sessions" => GoalMetric::Sessions,
+            "duration" => GoalMetric::Duration,
+            _ => GoalMetric::Words,
+        }
+    }
+}
+
+/// 写作目标
+#[derive(Debug, Clone, Serialize)]
+pub struct WritingGoal {
+    pub id: i64,
+    pub period: GoalPeriod,
+    pub metric: GoalMetric,
+    /// 目标值（时长目标以秒为单位）
+    pub target: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 设置目标请求（同周期同指标的目标已存在时更新目标值）
+#[derive(Debug, Deserialize)]
+pub struct SetWritingGoalRequest {
+    pub period: GoalPeriod,
+    pub metric: GoalMetric,
+    pub target: i64,
+}
+
+/// 目标在当前周期内的进度
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    pub goal: WritingGoal,
+    /// 周期开始日期（含）
+    pub period_start: String,
+    /// 周期结束日期（含）
+    pub period_end: String,
+    /// 当前周期已完成的值
+    pub current: i64,
+    /// 完成百分比（达成后可超过 100）
+    pub percent: f64,
+    pub achieved: bool,
+}
@@ -0,0 +1,76 @@
+//! 热力图数据模型
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 热力图指标
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeatmapMetric {
+    /// 写作字数
+    #[default]
+    Words,
+    /// 写作次数
+    Sessions,
+    /// 写作分钟数
+    Minutes,
+    /// 完成的任务数（含重复任务的每次完成）
+    TasksCompleted,
+}
+
+/// 热力图请求
+///
+/// 指定 `year` 时取该自然年；否则取 `start_date` ~ `end_date`，
+/// 缺省的开始日期为结束日期前 365 天，缺省的结束日期为今天。
+#[derive(Debug, Deserialize)]
+pub struct HeatmapRequest {
+    #[serde(default)]
+    pub year: Option<i32>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub metric: HeatmapMetric,
+    /// 强度等级数（不含表示无数据的 0 级）
+    #[serde(default = "default_levels")]
+    pub levels: usize,
+}
+
+impl Default for HeatmapRequest {
+    fn default() -> Self {
+        HeatmapRequest {
+            year: None,
+            start_date: None,
+            end_date: None,
+            metric: HeatmapMetric::default(),
+            levels: default_levels(),
+        }
+    }
+}
+
+fn default_levels() -> usize {
+    4
+}
+
+/// 热力图数据点（只返回有数据的日期）
+#[derive(Debug, Clone, Serialize)]
+pub struct HeatmapEntry {
+    pub date: String,
+    pub value: i64,
+    /// 强度等级（1 ~ levels）
+    pub level: usize,
+}
+
+/// 热力图
+#[derive(Debug, Clone, Serialize)]
+pub struct Heatmap {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub metric: HeatmapMetric,
+    /// 各等级的下限：`thresholds[i]` 是 `i + 1` 级的最小值（按分位数计算）
+    pub thresholds: Vec<i64>,
+    pub max_value: i64,
+    pub total: i64,
+    pub entries: Vec<HeatmapEntry>,
+}
@@ -5,3 +5,15 @@
 pub mod task;
 pub mod writing;
 pub mod plan;
+pub mod settings;
+pub mod tag;
+pub mod task_list;
+pub mod search;
+pub mod draft;
+pub mod revision;
+pub mod goal;
+pub mod streak;
+pub mod analytics;
+pub mod heatmap;
+pub mod task_event;
+pub mod plan_event;
@@ -30,6 +30,36 @@ impl PlanStatus {
     }
 }
 
+/// 错过计划日后的补救方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CatchUpMode {
+    /// 所有未完成的条目整体顺延，从今天继续
+    Shift,
+    /// 错过的条目移到计划末尾，其余条目日期不变
+    Append,
+    /// 跳过错过的条目，日期不变
+    Skip,
+}
+
+impl CatchUpMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CatchUpMode::Shift => "shift",
+            CatchUpMode::Append => "append",
+            CatchUpMode::Skip => "skip",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "shift" => CatchUpMode::Shift,
+            "append" => CatchUpMode::Append,
+            _ => CatchUpMode::Skip,
+        }
+    }
+}
+
 /// 写作计划实体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WritingPlan {
@@ -45,6 +75,31 @@ pub struct WritingPlan {
     /// 已完成天数（关联 writings 表计算）
     #[serde(default)]
     pub completed_days: i32,
+    /// 错过计划日后的补救方式
+    #[serde(default = "default_catch_up_mode")]
+    pub catch_up_mode: CatchUpMode,
+    /// 本次暂停的开始时间（未暂停时为空）
+    #[serde(default)]
+    pub paused_at: Option<String>,
+    /// 最近一次恢复的时间
+    #[serde(default)]
+    pub resumed_at: Option<String>,
+    /// 累计暂停时长（秒，含尚未恢复的本次暂停）
+    #[serde(default)]
+    pub paused_seconds: i64,
+    /// 完成时间（手动或自动完成时记录，重新开始后清空）
+    #[serde(default)]
+    pub completed_at: Option<String>,
+    /// 每周写作的日子（1 = 周一 … 7 = 周日），为空表示每天
+    #[serde(default)]
+    pub writing_weekdays: Vec<u32>,
+    /// 不安排写作的日期（YYYY-MM-DD），如节假日
+    #[serde(default)]
+    pub excluded_dates: Vec<String>,
+}
+
+fn default_catch_up_mode() -> CatchUpMode {
+    CatchUpMode::Skip
 }
 
 fn default_status() -> PlanStatus {
@@ -111,6 +166,12 @@ pub struct ImportPlanRequest {
     #[serde(alias = "startDate")]
     pub start_date: String,
     pub days: Vec<ImportPlanDayItem>,
+    /// 每周写作的日子（1 = 周一 … 7 = 周日），为空表示每天
+    #[serde(default, alias = "writingWeekdays")]
+    pub writing_weekdays: Vec<u32>,
+    /// 不安排写作的日期（YYYY-MM-DD）
+    #[serde(default, alias = "excludedDates")]
+    pub excluded_dates: Vec<String>,
 }
 
 /// 导入计划中的每日条目
@@ -135,6 +196,12 @@ pub struct GeneratePlanRequest {
     /// 难度：beginner / intermediate / advanced
     #[serde(default = "default_difficulty")]
     pub difficulty: String,
+    /// 每周写作的日子（1 = 周一 … 7 = 周日），为空表示每天
+    #[serde(default)]
+    pub writing_weekdays: Vec<u32>,
+    /// 不安排写作的日期（YYYY-MM-DD）
+    #[serde(default)]
+    pub excluded_dates: Vec<String>,
 }
 
 fn default_difficulty() -> String {
@@ -148,6 +215,32 @@ pub struct UpdatePlanRequest {
     pub name: Option<String>,
     pub theme: Option<String>,
     pub start_date: Option<String>,
+    pub catch_up_mode: Option<CatchUpMode>,
+    pub writing_weekdays: Option<Vec<u32>>,
+    pub excluded_dates: Option<Vec<String>>,
+    /// 开始日期或写作日规则变化时是否重排未完成条目的日期，为空时不重排
+    #[serde(default)]
+    pub reflow_days: Option<bool>,
+}
+
+/// 补救错过的计划日的请求
+#[derive(Debug, Deserialize)]
+pub struct CatchUpPlanRequest {
+    pub plan_id: i64,
+    /// 本次使用的补救方式，为空时使用计划自身的设置
+    #[serde(default)]
+    pub mode: Option<CatchUpMode>,
+}
+
+/// 补救结果
+#[derive(Debug, Clone, Serialize)]
+pub struct CatchUpResult {
+    pub mode: CatchUpMode,
+    /// 今天之前未完成的条目数
+    pub missed_days: i32,
+    /// 日期被改动的条目数
+    pub rescheduled_days: i32,
+    pub plan: PlanWithDays,
 }
 
 /// 计划 + 每日条目的完整视图（用于前端展示）
@@ -165,6 +258,7 @@ pub struct PlanWithDays {
 pub struct TodayWritingTask {
     pub plan_name: String,
     pub plan_id: i64,
+    pub plan_day_id: i64,
     pub day_number: i32,
     pub title: String,
     pub prompt: String,
@@ -0,0 +1,91 @@
+//! 写作计划生命周期事件数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 计划事件类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanEventType {
+    Created,
+    Paused,
+    /// 从暂停恢复为进行中
+    Resumed,
+    Completed,
+    /// 已完成的计划重新开始
+    Reopened,
+}
+
+impl PlanEventType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PlanEventType::Created => "created",
+            PlanEventType::Paused => "paused",
+            PlanEventType::Resumed => "resumed",
+            PlanEventType::Completed => "completed",
+            PlanEventType::Reopened => "reopened",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "paused" => PlanEventType::Paused,
+            "resumed" => PlanEventType::Resumed,
+            "completed" => PlanEventType::Completed,
+            "reopened" => PlanEventType::Reopened,
+            _ => PlanEventType::Created,
+        }
+    }
+}
+
+/// 计划完成的原因
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanCompletionReason {
+    /// 手动标记完成
+    Manual,
+    /// 所有条目都已写作
+    AllDaysWritten,
+    /// 最后一个计划日已过
+    EndDatePassed,
+}
+
+impl PlanCompletionReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PlanCompletionReason::Manual => "manual",
+            PlanCompletionReason::AllDaysWritten => "all_days_written",
+            PlanCompletionReason::EndDatePassed => "end_date_passed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "manual" => Some(PlanCompletionReason::Manual),
+            "all_days_written" => Some(PlanCompletionReason::AllDaysWritten),
+            "end_date_passed" => Some(PlanCompletionReason::EndDatePassed),
+            _ => None,
+        }
+    }
+}
+
+/// 计划事件
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanEvent {
+    pub id: i64,
+    pub plan_id: i64,
+    pub event_type: PlanEventType,
+    pub occurred_at: String,
+    /// 完成原因（仅完成事件）
+    pub reason: Option<PlanCompletionReason>,
+}
+
+/// 计划自动完成时发给前端的事件内容（事件名 `plan-completed`）
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanCompletedPayload {
+    pub plan_id: i64,
+    pub plan_name: String,
+    pub completed_at: String,
+    pub reason: PlanCompletionReason,
+    pub completed_days: i32,
+    pub total_days: i32,
+}
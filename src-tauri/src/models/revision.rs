@@ -0,0 +1,69 @@
+//! 写作记录修订历史数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 写作记录的一个历史版本（完整快照）
+#[derive(Debug, Clone, Serialize)]
+pub struct WritingRevision {
+    pub id: i64,
+    pub writing_id: i64,
+    /// 版本号（每篇写作从 1 开始递增）
+    pub revision_number: i32,
+    pub title: String,
+    pub content: String,
+    pub word_count: i32,
+    pub created_at: String,
+}
+
+/// 比较粒度
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffGranularity {
+    /// 按行比较
+    Line,
+    /// 按字比较：中文逐字，英文等按整词
+    #[default]
+    Char,
+}
+
+/// 比较两个版本的请求
+#[derive(Debug, Deserialize)]
+pub struct DiffRevisionsRequest {
+    /// 旧版本 ID
+    pub from_revision_id: i64,
+    /// 新版本 ID，为空表示与写作记录的当前内容比较
+    #[serde(default)]
+    pub to_revision_id: Option<i64>,
+    #[serde(default)]
+    pub granularity: DiffGranularity,
+}
+
+/// 差异片段类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// 差异片段（连续的同类操作合并为一段）
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// 两个版本的比较结果（基于去掉 HTML 标签后的纯文本）
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionDiff {
+    pub from_revision_id: i64,
+    /// 为空表示与当前内容比较
+    pub to_revision_id: Option<i64>,
+    pub granularity: DiffGranularity,
+    pub segments: Vec<DiffSegment>,
+    /// 新增的非空白字符数
+    pub inserted_chars: i32,
+    /// 删除的非空白字符数
+    pub deleted_chars: i32,
+}
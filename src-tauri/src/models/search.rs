@@ -0,0 +1,51 @@
+//! 全文搜索数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 搜索结果的来源类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    /// 写作记录（标题 + 正文）
+    Writing,
+    /// 写作计划中的每日题目（标题 + 提示）
+    PlanDay,
+    /// 任务（标题 + 描述）
+    Task,
+}
+
+/// 搜索请求
+#[derive(Debug, Deserialize)]
+pub struct SearchRequest {
+    /// 搜索词，多个词用空格分隔（需同时匹配）
+    pub query: String,
+    /// 限定来源类型，为空表示全部
+    #[serde(default)]
+    pub kinds: Vec<SearchKind>,
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+}
+
+fn default_search_limit() -> usize {
+    50
+}
+
+/// 单条搜索结果
+///
+/// `title` 和 `snippet` 为 HTML 转义后的纯文本（写作正文已去掉标签），
+/// 命中的文字用 `<mark>` / `</mark>` 包裹，可直接作为 HTML 渲染。
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    /// 写作记录 / 每日题目 / 任务的 ID
+    pub id: i64,
+    pub title: String,
+    /// 正文中命中位置附近的摘要
+    pub snippet: String,
+    /// 写作日期 / 计划日期 / 截止日期
+    pub date: Option<String>,
+    /// 每日题目所属的写作计划 ID
+    pub plan_id: Option<i64>,
+    /// 来源内的相关度得分（bm25，越小越相关；只有短词时为 0），不同来源之间不可比较
+    pub score: f64,
+}
@@ -0,0 +1,82 @@
+//! 应用设置数据模型
+//!
+//! 需要后端读取的设置（如后台提醒）保存在数据库 `app_settings` 表中，
+//! 纯前端的外观设置仍保存在 localStorage。
+
+use serde::{Deserialize, Serialize};
+
+/// 每日写作提醒设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WritingReminderSettings {
+    /// 是否开启每日写作提醒（默认关闭，由用户在设置中开启）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 提醒时间（HH:MM）
+    #[serde(default = "default_remind_at")]
+    pub remind_at: String,
+    /// 是否开启"连续记录即将中断"的二次提醒
+    #[serde(default = "default_true")]
+    pub follow_up_enabled: bool,
+    /// 二次提醒时间（HH:MM），届时今日计划仍未写作才会提醒
+    #[serde(default = "default_follow_up_at")]
+    pub follow_up_at: String,
+}
+
+impl Default for WritingReminderSettings {
+    fn default() -> Self {
+        WritingReminderSettings {
+            enabled: false,
+            remind_at: default_remind_at(),
+            follow_up_enabled: true,
+            follow_up_at: default_follow_up_at(),
+        }
+    }
+}
+
+/// 连续写作天数的统计方式
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreakSettings {
+    /// 只统计达成全部每日目标的日子（没有每日目标时与任意写作等同）
+    #[serde(default)]
+    pub require_daily_goal: bool,
+    /// 每周固定的休息日（1 = 周一 … 7 = 周日），没有写作也不中断连续
+    #[serde(default)]
+    pub rest_weekdays: Vec<u32>,
+    /// 冻结日（YYYY-MM-DD），没有写作也不中断连续
+    #[serde(default)]
+    pub freeze_dates: Vec<String>,
+}
+
+/// 计划自动完成的条件
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanCompletionPolicy {
+    /// 只能手动标记完成
+    Manual,
+    /// 所有条目都已写作
+    #[default]
+    AllDaysWritten,
+    /// 最后一个计划日已过（不论是否全部写完）
+    EndDatePassed,
+    /// 满足以上任一条件
+    Either,
+}
+
+/// 计划自动完成设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlanCompletionSettings {
+    #[serde(default)]
+    pub policy: PlanCompletionPolicy,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_remind_at() -> String {
+    "20:00".to_string()
+}
+
+fn default_follow_up_at() -> String {
+    "22:00".to_string()
+}
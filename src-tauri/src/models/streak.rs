@@ -0,0 +1,39 @@
+//! 连续写作天数数据模型
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// 一段连续写作（起止均为写作日，中间可能夹有休息日 / 冻结日）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreakRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// 写作天数（不含跳过的休息日 / 冻结日）
+    pub days: i32,
+}
+
+/// 一段中断（连续的缺勤日，中间可能夹有休息日 / 冻结日）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreakGap {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// 缺勤天数（不含跳过的休息日 / 冻结日）
+    pub days: i32,
+}
+
+/// 连续写作统计结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StreakSummary {
+    /// 计算所依据的「今天」
+    pub today: Option<NaiveDate>,
+    /// 今天是否已写作（今天未写作不会中断当前连续）
+    pub today_written: bool,
+    pub current_streak: i32,
+    /// 当前连续（已中断时为空）
+    pub current: Option<StreakRange>,
+    pub max_streak: i32,
+    /// 最长连续（长度相同时取最近的一段）
+    pub longest: Option<StreakRange>,
+    /// 第一次写作以来的所有中断，按时间升序
+    pub gaps: Vec<StreakGap>,
+}
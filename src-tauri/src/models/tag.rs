@@ -0,0 +1,55 @@
+//! 标签数据模型
+//!
+//! 标签可同时用于任务和写作记录。
+
+use serde::{Deserialize, Serialize};
+
+/// 标签实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: Option<i64>,
+    pub name: String,
+    /// 显示颜色（如 #3b82f6）
+    #[serde(default)]
+    pub color: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// 创建标签请求
+#[derive(Debug, Deserialize)]
+pub struct CreateTagRequest {
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// 更新标签请求
+#[derive(Debug, Deserialize)]
+pub struct UpdateTagRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+/// 设置任务 / 写作记录标签的请求（整体替换）
+#[derive(Debug, Deserialize)]
+pub struct SetTagsRequest {
+    /// 任务 ID 或写作记录 ID
+    pub target_id: i64,
+    pub tag_ids: Vec<i64>,
+}
+
+/// 标签使用统计
+#[derive(Debug, Clone, Serialize)]
+pub struct TagStats {
+    #[serde(flatten)]
+    pub tag: Tag,
+    /// 关联任务数
+    pub task_count: i32,
+    /// 关联的未完成任务数
+    pub open_task_count: i32,
+    /// 关联写作记录数
+    pub writing_count: i32,
+    /// 关联写作记录的总字数
+    pub writing_words: i64,
+}
@@ -68,18 +68,171 @@ pub struct Task {
     #[serde(default = "default_priority")]
     pub priority: Priority,
     pub due_date: Option<NaiveDate>,
+    /// 截止时间（HH:MM，可选）
+    #[serde(default)]
+    pub due_time: Option<String>,
     #[serde(default)]
     pub repeat: Option<RepeatType>,
+    /// RFC 5545 重复规则（不含 `RRULE:` 前缀），设置后优先于 `repeat`
+    #[serde(default)]
+    pub rrule: Option<String>,
+    /// 重复规则的排除日期（YYYY-MM-DD）
+    #[serde(default)]
+    pub exdates: Vec<String>,
     #[serde(default)]
     pub completed: bool,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    /// 清单项全部完成时自动完成任务
+    #[serde(default)]
+    pub auto_complete: bool,
+    /// 清单项总数
+    #[serde(default)]
+    pub item_count: i32,
+    /// 已完成的清单项数
+    #[serde(default)]
+    pub completed_item_count: i32,
+    /// 清单完成比例（0.0 ~ 1.0），没有清单项时为空
+    #[serde(default)]
+    pub progress: Option<f64>,
+    /// 关联的标签 ID
+    #[serde(default)]
+    pub tag_ids: Vec<i64>,
+    /// 所属任务列表，为空表示位于收件箱
+    #[serde(default)]
+    pub list_id: Option<i64>,
+    /// 完成时间（未完成时为空；重复任务只在全部发生完成后才有）
+    #[serde(default)]
+    pub completed_at: Option<String>,
+}
+
+impl Task {
+    /// 是否为重复任务（固定类型或 RRULE）
+    pub fn is_repeating(&self) -> bool {
+        self.repeat.is_some() || self.rrule.is_some()
+    }
 }
 
 fn default_priority() -> Priority {
     Priority::Medium
 }
 
+/// 按截止日期分组的任务（周从周一开始）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TaskBuckets {
+    /// 截止日期早于今天
+    pub overdue: Vec<Task>,
+    pub today: Vec<Task>,
+    pub tomorrow: Vec<Task>,
+    /// 明天之后、本周日及之前
+    pub this_week: Vec<Task>,
+    /// 本周之后
+    pub later: Vec<Task>,
+    /// 没有截止日期
+    pub no_date: Vec<Task>,
+}
+
+/// 把逾期任务顺延到今天的请求
+#[derive(Debug, Default, Deserialize)]
+pub struct RollOverTasksRequest {
+    /// 只顺延指定的任务，为空表示所有逾期任务
+    #[serde(default)]
+    pub task_ids: Option<Vec<i64>>,
+}
+
+/// 按重复方式筛选任务
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepeatFilter {
+    /// 不重复
+    None,
+    /// 任意重复方式
+    Any,
+    Daily,
+    Weekly,
+    Monthly,
+    /// 使用 RRULE 自定义规则
+    Custom,
+}
+
+/// 任务排序字段
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortKey {
+    /// 未完成在前
+    Completed,
+    /// 截止日期和时间，没有截止日期的总排在最后
+    DueDate,
+    /// 升序为低 → 高
+    Priority,
+    CreatedAt,
+    UpdatedAt,
+    CompletedAt,
+    /// 标题（不区分大小写）
+    Title,
+}
+
+/// 单个排序条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSort {
+    pub key: TaskSortKey,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// 任务查询条件，所有筛选项之间为“且”关系
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TaskQuery {
+    /// 按完成状态筛选，为空表示不限
+    #[serde(default)]
+    pub completed: Option<bool>,
+    /// 优先级属于其中之一，为空表示不限
+    #[serde(default)]
+    pub priorities: Vec<Priority>,
+    /// 截止日期下限（含）
+    #[serde(default)]
+    pub due_from: Option<String>,
+    /// 截止日期上限（含）
+    #[serde(default)]
+    pub due_to: Option<String>,
+    /// 指定截止日期范围时，是否同时包含没有截止日期的任务
+    #[serde(default)]
+    pub include_undated: bool,
+    /// 按重复方式筛选
+    #[serde(default)]
+    pub repeat: Option<RepeatFilter>,
+    /// 标题或描述包含的文字（不区分大小写）
+    #[serde(default)]
+    pub text: Option<String>,
+    /// 包含任一指定标签
+    #[serde(default)]
+    pub tag_ids: Vec<i64>,
+    /// 只查询指定列表中的任务（可以是已归档列表）
+    #[serde(default)]
+    pub list_id: Option<i64>,
+    /// 只查询收件箱中未归属列表的任务
+    #[serde(default)]
+    pub inbox: bool,
+    /// 排序条件，为空时按完成状态、截止日期、优先级（高在前）、创建时间（新在前）排序
+    #[serde(default)]
+    pub sort: Vec<TaskSort>,
+    /// 每页条数，为空表示不分页
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+/// 任务查询结果
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskPage {
+    pub tasks: Vec<Task>,
+    /// 符合条件的任务总数（不受分页影响）
+    pub total: i64,
+    pub offset: u32,
+    pub limit: Option<u32>,
+}
+
 /// 创建任务的请求参数
 #[derive(Debug, Deserialize)]
 pub struct CreateTaskRequest {
@@ -90,7 +243,18 @@ pub struct CreateTaskRequest {
     pub priority: Priority,
     pub due_date: Option<String>,
     #[serde(default)]
+    pub due_time: Option<String>,
+    #[serde(default)]
     pub repeat: Option<RepeatType>,
+    #[serde(default)]
+    pub rrule: Option<String>,
+    #[serde(default)]
+    pub exdates: Vec<String>,
+    #[serde(default)]
+    pub auto_complete: bool,
+    /// 所属任务列表
+    #[serde(default)]
+    pub list_id: Option<i64>,
 }
 
 /// 更新任务的请求参数
@@ -101,8 +265,140 @@ pub struct UpdateTaskRequest {
     pub description: Option<String>,
     pub priority: Option<Priority>,
     pub due_date: Option<String>,
+    /// 传空字符串表示清除截止时间
+    #[serde(default)]
+    pub due_time: Option<String>,
     pub repeat: Option<RepeatType>,
+    /// 传空字符串表示清除 RRULE
+    #[serde(default)]
+    pub rrule: Option<String>,
+    #[serde(default)]
+    pub exdates: Option<Vec<String>>,
     pub completed: Option<bool>,
+    #[serde(default)]
+    pub auto_complete: Option<bool>,
+}
+
+/// 任务清单项（子任务）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskItem {
+    pub id: Option<i64>,
+    pub task_id: i64,
+    pub title: String,
+    #[serde(default)]
+    pub completed: bool,
+    /// 排序序号（从 0 开始）
+    #[serde(default)]
+    pub sort_order: i32,
+    pub created_at: Option<String>,
+}
+
+/// 任务 + 清单项的完整视图
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskDetail {
+    #[serde(flatten)]
+    pub task: Task,
+    pub items: Vec<TaskItem>,
+}
+
+/// 添加清单项请求
+#[derive(Debug, Deserialize)]
+pub struct AddTaskItemRequest {
+    pub task_id: i64,
+    pub title: String,
+}
+
+/// 更新清单项请求
+#[derive(Debug, Deserialize)]
+pub struct UpdateTaskItemRequest {
+    pub id: i64,
+    pub title: Option<String>,
+    pub completed: Option<bool>,
+}
+
+/// 清单项重新排序请求
+#[derive(Debug, Deserialize)]
+pub struct ReorderTaskItemsRequest {
+    pub task_id: i64,
+    /// 按新顺序排列的清单项 ID
+    pub item_ids: Vec<i64>,
+}
+
+/// 重复任务的单次完成记录
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskCompletion {
+    pub id: i64,
+    pub task_id: i64,
+    /// 本次完成对应的截止日期（无截止日期时为完成当天）
+    pub occurrence_date: String,
+    pub completed_at: String,
+}
+
+/// 提醒类型
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderKind {
+    /// 截止前若干分钟
+    Before,
+    /// 截止当天的固定时间（如早上 9 点）
+    OnDay,
+}
+
+impl ReminderKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ReminderKind::Before => "before",
+            ReminderKind::OnDay => "on_day",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "on_day" => ReminderKind::OnDay,
+            _ => ReminderKind::Before,
+        }
+    }
+}
+
+/// 任务提醒
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskReminder {
+    pub id: Option<i64>,
+    pub task_id: i64,
+    pub kind: ReminderKind,
+    /// `Before` 时的提前分钟数
+    #[serde(default)]
+    pub offset_minutes: Option<i32>,
+    /// `OnDay` 时的提醒时间（HH:MM）
+    #[serde(default)]
+    pub at_time: Option<String>,
+}
+
+/// 设置任务提醒的请求（整体替换）
+#[derive(Debug, Deserialize)]
+pub struct SetTaskRemindersRequest {
+    pub task_id: i64,
+    pub reminders: Vec<ReminderInput>,
+}
+
+/// 单条提醒设置
+#[derive(Debug, Deserialize)]
+pub struct ReminderInput {
+    pub kind: ReminderKind,
+    #[serde(default)]
+    pub offset_minutes: Option<i32>,
+    #[serde(default)]
+    pub at_time: Option<String>,
+}
+
+/// 到期待发送的提醒（由后台调度器使用）
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    pub reminder_id: i64,
+    pub task_id: i64,
+    pub task_title: String,
+    pub due_date: NaiveDate,
+    pub due_time: Option<String>,
 }
 
 /// 批量导入任务的单条数据
@@ -117,5 +413,26 @@ pub struct ImportTaskItem {
     #[serde(default, alias = "due")]
     pub due: Option<String>,
     #[serde(default)]
+    pub due_time: Option<String>,
+    #[serde(default)]
     pub repeat: Option<RepeatType>,
+    #[serde(default)]
+    pub rrule: Option<String>,
+}
+
+/// 预览重复规则的请求
+#[derive(Debug, Deserialize)]
+pub struct PreviewRRuleRequest {
+    pub rrule: String,
+    /// 规则起点 (YYYY-MM-DD)
+    pub start_date: String,
+    /// 预览条数
+    #[serde(default = "default_preview_count")]
+    pub count: usize,
+    #[serde(default)]
+    pub exdates: Vec<String>,
+}
+
+fn default_preview_count() -> usize {
+    10
 }
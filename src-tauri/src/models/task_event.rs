@@ -0,0 +1,87 @@
+//! 任务事件与任务统计数据模型
+
+use serde::{Deserialize, Serialize};
+
+/// 任务事件类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskEventType {
+    Created,
+    /// 完成（重复任务每完成一次记一条）
+    Completed,
+    /// 重新打开已完成的任务
+    Reopened,
+    /// 截止日期变更
+    Rescheduled,
+}
+
+impl TaskEventType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskEventType::Created => "created",
+            TaskEventType::Completed => "completed",
+            TaskEventType::Reopened => "reopened",
+            TaskEventType::Rescheduled => "rescheduled",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "completed" => TaskEventType::Completed,
+            "reopened" => TaskEventType::Reopened,
+            "rescheduled" => TaskEventType::Rescheduled,
+            _ => TaskEventType::Created,
+        }
+    }
+}
+
+/// 任务事件
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskEvent {
+    pub id: i64,
+    pub task_id: i64,
+    pub event_type: TaskEventType,
+    pub occurred_at: String,
+    /// 事件发生时的截止日期（完成重复任务时为本次发生的日期；改期时为新日期）
+    pub due_date: Option<String>,
+    /// 改期前的截止日期
+    pub previous_due_date: Option<String>,
+}
+
+/// 任务统计请求（缺省为截至今天的最近 30 天）
+#[derive(Debug, Default, Deserialize)]
+pub struct TaskStatsRequest {
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+}
+
+/// 每天完成的任务数
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskDailyCount {
+    pub date: String,
+    pub count: i64,
+}
+
+/// 任务完成统计（已被重新打开的完成不计入）
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStats {
+    pub start_date: String,
+    pub end_date: String,
+    /// 范围内新建的任务数
+    pub created: i64,
+    pub completed: i64,
+    /// 在截止日期当天或之前完成
+    pub completed_on_time: i64,
+    /// 晚于截止日期完成
+    pub completed_overdue: i64,
+    /// 没有截止日期的完成
+    pub completed_without_due: i64,
+    /// 按时完成率（按时 / 有截止日期的完成），没有有截止日期的完成时为空
+    pub on_time_rate: Option<f64>,
+    /// 平均前置时间（小时）：非重复任务从创建到完成的时长
+    pub avg_lead_time_hours: Option<f64>,
+    /// 范围内每天的完成数（含 0）
+    pub per_day: Vec<TaskDailyCount>,
+}
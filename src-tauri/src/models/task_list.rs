@@ -0,0 +1,62 @@
+//! 任务列表（项目）数据模型
+//!
+//! 任务可以归属到一个列表（如"工作"、"小说资料"），未归属的任务位于收件箱。
+
+use serde::{Deserialize, Serialize};
+
+/// 任务列表实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskList {
+    pub id: Option<i64>,
+    pub name: String,
+    /// 显示颜色（如 #3b82f6）
+    #[serde(default)]
+    pub color: Option<String>,
+    /// 排序序号（从 0 开始）
+    #[serde(default)]
+    pub sort_order: i32,
+    /// 已归档的列表及其任务不出现在默认任务视图中
+    #[serde(default)]
+    pub archived: bool,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    /// 列表中的任务数
+    #[serde(default)]
+    pub task_count: i32,
+    /// 列表中未完成的任务数
+    #[serde(default)]
+    pub open_task_count: i32,
+}
+
+/// 创建任务列表请求
+#[derive(Debug, Deserialize)]
+pub struct CreateTaskListRequest {
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// 更新任务列表请求
+#[derive(Debug, Deserialize)]
+pub struct UpdateTaskListRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    pub color: Option<String>,
+    pub archived: Option<bool>,
+}
+
+/// 任务列表重新排序请求
+#[derive(Debug, Deserialize)]
+pub struct ReorderTaskListsRequest {
+    /// 按新顺序排列的列表 ID
+    pub list_ids: Vec<i64>,
+}
+
+/// 在列表之间移动任务的请求
+#[derive(Debug, Deserialize)]
+pub struct MoveTasksRequest {
+    pub task_ids: Vec<i64>,
+    /// 目标列表 ID，为空表示移回收件箱
+    #[serde(default)]
+    pub list_id: Option<i64>,
+}
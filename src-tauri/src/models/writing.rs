@@ -1,6 +1,7 @@
 //! 写作记录数据模型
 
 use serde::{Deserialize, Serialize};
+use crate::models::streak::StreakRange;
 
 /// 写作记录实体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +18,39 @@ pub struct Writing {
     /// 写作日期
     pub written_date: String,
     pub created_at: Option<String>,
+    /// 最后修改时间
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// 关联的标签 ID
+    #[serde(default)]
+    pub tag_ids: Vec<i64>,
+    /// 字数统计明细
+    #[serde(default)]
+    pub metrics: TextMetrics,
+}
+
+/// 正文统计明细（规则见 `text_metrics` 模块）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TextMetrics {
+    /// 字数 = 中日韩文字数 + 拉丁词数
+    pub word_count: i32,
+    /// 中日韩文字数
+    pub cjk_chars: i32,
+    /// 拉丁字母 / 数字词数
+    pub latin_words: i32,
+    /// 标点符号数
+    pub punctuation: i32,
+    /// 段落数
+    pub paragraphs: i32,
+}
+
+/// 重新计算字数的结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RecomputeMetricsResult {
+    /// 处理的写作记录数
+    pub scanned: i32,
+    /// 字数发生变化的记录数
+    pub changed: i32,
 }
 
 /// 保存写作记录的请求
@@ -26,10 +60,28 @@ pub struct SaveWritingRequest {
     pub plan_day_id: Option<i64>,
     pub title: String,
     pub content: String,
+    /// 已废弃：字数由后端根据正文计算，传入的值会被忽略
+    #[serde(default)]
     pub word_count: i32,
     pub duration_seconds: i32,
 }
 
+/// 更新写作记录的请求
+///
+/// 修改正文时字数按新内容重新计算。
+#[derive(Debug, Deserialize)]
+pub struct UpdateWritingRequest {
+    pub id: i64,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    /// 改为关联到另一个计划日条目
+    #[serde(default)]
+    pub plan_day_id: Option<i64>,
+    /// 解除与计划日条目的关联（转为自由写作）
+    #[serde(default)]
+    pub clear_plan_day: bool,
+}
+
 /// 写作统计摘要
 #[derive(Debug, Clone, Serialize)]
 pub struct WritingStats {
@@ -43,25 +95,23 @@ pub struct WritingStats {
     pub current_streak: i32,
     /// 最长连续天数
     pub max_streak: i32,
+    /// 最长连续的起止日期
+    pub longest_streak: Option<StreakRange>,
+    /// 连续天数是否只统计达成每日目标的日子
+    pub streak_requires_goal: bool,
     /// 平均每次字数
     pub avg_words_per_session: f64,
     /// 平均每次时长（秒）
     pub avg_duration_per_session: f64,
 }
 
-/// 写作热力图数据点
-#[derive(Debug, Clone, Serialize)]
-pub struct HeatmapEntry {
-    pub date: String,
-    pub word_count: i32,
-    /// 写作次数
-    pub count: i32,
-}
-
 /// 按日期查询写作记录的过滤条件
 #[derive(Debug, Deserialize)]
 pub struct WritingFilter {
     pub start_date: Option<String>,
     pub end_date: Option<String>,
     pub plan_id: Option<i64>,
+    /// 包含任一指定标签
+    #[serde(default)]
+    pub tag_ids: Option<Vec<i64>>,
 }
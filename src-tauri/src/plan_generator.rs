@@ -26,6 +26,8 @@ pub fn generate_writing_plan(req: &GeneratePlanRequest) -> AppResult<ImportPlanR
         theme: Some(req.theme.clone()),
         start_date: req.start_date.clone(),
         days,
+        writing_weekdays: req.writing_weekdays.clone(),
+        excluded_dates: req.excluded_dates.clone(),
     })
 }
 
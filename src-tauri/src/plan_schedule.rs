@@ -0,0 +1,203 @@
+//! 写作计划日程计算
+//!
+//! 纯函数，不访问数据库。按每周写作日和排除日期（如节假日）推算计划条目的日期：
+//! 第 N 天落在开始日期之后（含）的第 N 个写作日上。
+
+use std::collections::{BTreeSet, HashSet};
+use chrono::{Datelike, Duration, NaiveDate};
+use crate::errors::{AppError, AppResult};
+
+/// 计划的写作日规则
+#[derive(Debug, Clone, Default)]
+pub struct PlanSchedule {
+    /// 每周写作的日子（1 = 周一 … 7 = 周日），为空表示每天
+    weekdays: BTreeSet<u32>,
+    /// 不安排写作的日期
+    excluded: HashSet<NaiveDate>,
+}
+
+impl PlanSchedule {
+    /// 校验并构造规则；每周七天都写时等同于不限制
+    pub fn new(weekdays: &[u32], excluded_dates: &[String]) -> AppResult<Self> {
+        if let Some(day) = weekdays.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(AppError::Business(format!("写作日无效（应为 1~7）: {}", day)));
+        }
+        let mut weekdays: BTreeSet<u32> = weekdays.iter().copied().collect();
+        if weekdays.len() == 7 {
+            weekdays.clear();
+        }
+
+        let excluded = excluded_dates.iter()
+            .map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|_| AppError::Business(format!("日期格式无效（应为 YYYY-MM-DD）: {}", s))))
+            .collect::<AppResult<HashSet<_>>>()?;
+
+        Ok(PlanSchedule { weekdays, excluded })
+    }
+
+    /// 规范化后的每周写作日（升序，为空表示每天）
+    pub fn weekdays(&self) -> Vec<u32> {
+        self.weekdays.iter().copied().collect()
+    }
+
+    /// 规范化后的排除日期（升序）
+    pub fn excluded_dates(&self) -> Vec<String> {
+        let mut dates: Vec<NaiveDate> = self.excluded.iter().copied().collect();
+        dates.sort_unstable();
+        dates.iter().map(|d| d.format("%Y-%m-%d").to_string()).collect()
+    }
+
+    /// 这一天是否安排写作
+    pub fn is_writing_day(&self, date: NaiveDate) -> bool {
+        (self.weekdays.is_empty() || self.weekdays.contains(&date.weekday().number_from_monday()))
+            && !self.excluded.contains(&date)
+    }
+
+    /// 不早于 `date` 的第一个写作日
+    ///
+    /// 每周至少有一个写作日、排除日期有限，只有超出可表示的日期范围时才会找不到。
+    pub fn next_on_or_after(&self, date: NaiveDate) -> AppResult<NaiveDate> {
+        date.iter_days()
+            .find(|d| self.is_writing_day(*d))
+            .ok_or_else(|| AppError::Business(format!("{} 之后没有可安排的写作日", date)))
+    }
+
+    /// 不早于 `date`、且不在 `taken` 中的第一个写作日
+    pub fn next_free_on_or_after(&self, date: NaiveDate, taken: &HashSet<NaiveDate>) -> AppResult<NaiveDate> {
+        let mut target = self.next_on_or_after(date)?;
+        while taken.contains(&target) {
+            target = self.next_on_or_after(target + Duration::days(1))?;
+        }
+        Ok(target)
+    }
+
+    /// 从 `start` 开始的第 `n` 个写作日（`n` 从 0 开始）
+    pub fn nth_date(&self, start: NaiveDate, n: usize) -> AppResult<NaiveDate> {
+        let mut date = self.next_on_or_after(start)?;
+        for _ in 0..n {
+            date = self.next_on_or_after(date + Duration::days(1))?;
+        }
+        Ok(date)
+    }
+
+    /// 把一组升序的日期整体后移 `days` 天，落在非写作日或 `taken` 中日期的顺延到下一个可用的写作日，
+    /// 并保证结果仍然严格递增（不会有两个条目挤在同一天）
+    pub fn shift(&self, dates: &[NaiveDate], days: i64, taken: &HashSet<NaiveDate>) -> AppResult<Vec<NaiveDate>> {
+        let mut shifted: Vec<NaiveDate> = Vec::with_capacity(dates.len());
+        for date in dates {
+            let mut target = *date + Duration::days(days);
+            if let Some(prev) = shifted.last() {
+                target = target.max(*prev + Duration::days(1));
+            }
+            shifted.push(self.next_free_on_or_after(target, taken)?);
+        }
+        Ok(shifted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn dates(list: &[&str]) -> Vec<NaiveDate> {
+        list.iter().map(|s| d(s)).collect()
+    }
+
+    fn schedule(weekdays: &[u32], excluded: &[&str]) -> PlanSchedule {
+        let excluded: Vec<String> = excluded.iter().map(|s| s.to_string()).collect();
+        PlanSchedule::new(weekdays, &excluded).unwrap()
+    }
+
+    /// 2026-10-02 是周五
+    const FRIDAY: &str = "2026-10-02";
+
+    #[test]
+    fn rejects_invalid_rules() {
+        assert!(PlanSchedule::new(&[0], &[]).is_err());
+        assert!(PlanSchedule::new(&[8], &[]).is_err());
+        assert!(PlanSchedule::new(&[], &["2026-02-30".to_string()]).is_err());
+    }
+
+    #[test]
+    fn normalizes_rules() {
+        let s = schedule(&[5, 1, 3, 1], &["2026-10-06", "2026-10-01"]);
+        assert_eq!(s.weekdays(), vec![1, 3, 5]);
+        assert_eq!(s.excluded_dates(), vec!["2026-10-01", "2026-10-06"]);
+        // 每周七天都写等同于不限制
+        assert!(schedule(&[1, 2, 3, 4, 5, 6, 7], &[]).weekdays().is_empty());
+    }
+
+    #[test]
+    fn every_day_by_default() {
+        let s = PlanSchedule::default();
+        assert_eq!(s.next_on_or_after(d(FRIDAY)).unwrap(), d(FRIDAY));
+        assert_eq!(s.nth_date(d(FRIDAY), 2).unwrap(), d("2026-10-04"));
+    }
+
+    #[test]
+    fn weekday_mask_skips_weekends() {
+        let s = schedule(&[1, 2, 3, 4, 5], &[]);
+        assert!(s.is_writing_day(d(FRIDAY)));
+        assert!(!s.is_writing_day(d("2026-10-03")));
+        assert_eq!(s.next_on_or_after(d("2026-10-03")).unwrap(), d("2026-10-05"));
+        assert_eq!(s.nth_date(d(FRIDAY), 1).unwrap(), d("2026-10-05"));
+        assert_eq!(s.nth_date(d("2026-10-03"), 0).unwrap(), d("2026-10-05"));
+    }
+
+    #[test]
+    fn excluded_dates_are_skipped() {
+        let s = schedule(&[], &["2026-10-03", "2026-10-04"]);
+        assert!(!s.is_writing_day(d("2026-10-03")));
+        assert_eq!(s.nth_date(d(FRIDAY), 1).unwrap(), d("2026-10-05"));
+
+        let s = schedule(&[1, 3, 5], &["2026-10-05"]);
+        assert_eq!(s.nth_date(d(FRIDAY), 1).unwrap(), d("2026-10-07"));
+        assert_eq!(s.nth_date(d(FRIDAY), 2).unwrap(), d("2026-10-09"));
+    }
+
+    #[test]
+    fn shift_moves_across_excluded_dates() {
+        let s = schedule(&[1, 2, 3, 4, 5], &["2026-10-06"]);
+        assert_eq!(
+            s.shift(&dates(&["2026-10-01", FRIDAY, "2026-10-05"]), 2, &HashSet::new()).unwrap(),
+            dates(&["2026-10-05", "2026-10-07", "2026-10-08"]),
+        );
+    }
+
+    #[test]
+    fn shift_keeps_dates_strictly_increasing() {
+        let s = schedule(&[1, 3, 5], &[]);
+        // 10-02 周五 +1 → 10-03 周六 → 10-05；10-05 周一 +1 → 10-06 → 10-07
+        assert_eq!(
+            s.shift(&dates(&[FRIDAY, "2026-10-05"]), 1, &HashSet::new()).unwrap(),
+            dates(&["2026-10-05", "2026-10-07"]),
+        );
+        // 目标日期相同时依次顺延到下一个写作日
+        assert_eq!(
+            s.shift(&dates(&[FRIDAY, "2026-10-03"]), 0, &HashSet::new()).unwrap(),
+            dates(&[FRIDAY, "2026-10-05"]),
+        );
+        assert_eq!(s.shift(&[], 3, &HashSet::new()).unwrap(), Vec::<NaiveDate>::new());
+    }
+
+    #[test]
+    fn shift_skips_taken_dates() {
+        let s = PlanSchedule::default();
+        let taken: HashSet<NaiveDate> = dates(&["2026-10-04", "2026-10-05"]).into_iter().collect();
+        assert_eq!(
+            s.shift(&dates(&[FRIDAY, "2026-10-03", "2026-10-08"]), 1, &taken).unwrap(),
+            dates(&["2026-10-03", "2026-10-06", "2026-10-09"]),
+        );
+        assert_eq!(s.next_free_on_or_after(d("2026-10-04"), &taken).unwrap(), d("2026-10-06"));
+    }
+
+    #[test]
+    fn errors_when_no_writing_day_is_representable() {
+        let s = schedule(&[1], &[]);
+        assert!(s.next_on_or_after(NaiveDate::MAX).is_err());
+    }
+}
@@ -0,0 +1,521 @@
+//! RFC 5545 重复规则（RRULE）解析与展开
+//!
+//! 任务只有日期没有时间，因此这里按天展开规则，支持的部分：
+//! - `FREQ`：DAILY / WEEKLY / MONTHLY / YEARLY
+//! - `INTERVAL`、`COUNT`、`UNTIL`（`YYYYMMDD` 或带时间的 `YYYYMMDDTHHMMSSZ`，只取日期）
+//! - `BYDAY`（可带序号，如 `2MO`、`-1FR`）、`BYMONTHDAY`（可为负数）、`BYMONTH`、`BYSETPOS`
+//! - `WKST` 只接受并忽略，周起始固定为周一
+//!
+//! EXDATE 不属于 RRULE 本身，由调用方以日期列表形式传入。
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use crate::errors::{AppError, AppResult};
+
+/// 单个规则最多展开的周期数，防止永远不会命中的规则（如 2 月 30 日）死循环
+const MAX_PERIODS: u32 = 10_000;
+
+/// 重复频率
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// 解析后的重复规则
+#[derive(Debug, Clone, PartialEq)]
+pub struct RRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDate>,
+    /// (序号, 星期)，序号为空表示周期内所有该星期
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+}
+
+impl RRule {
+    /// 解析 RRULE 字符串，允许带 `RRULE:` 前缀，大小写不敏感
+    pub fn parse(rule: &str) -> AppResult<Self> {
+        let body = normalize(rule);
+        if body.is_empty() {
+            return Err(invalid("规则为空"));
+        }
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut by_month = Vec::new();
+        let mut by_set_pos = Vec::new();
+
+        for part in body.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(&format!("无法识别的片段 \"{}\"", part)))?;
+
+            match key {
+                "FREQ" => {
+                    freq = Some(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(invalid(&format!("不支持的 FREQ \"{}\"", value))),
+                    });
+                }
+                "INTERVAL" => {
+                    interval = parse_number::<u32>(key, value)?;
+                    if interval == 0 {
+                        return Err(invalid("INTERVAL 必须大于 0"));
+                    }
+                }
+                "COUNT" => count = Some(parse_number::<u32>(key, value)?),
+                "UNTIL" => until = Some(parse_until(value)?),
+                "BYDAY" => {
+                    for item in value.split(',') {
+                        by_day.push(parse_by_day(item)?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for item in value.split(',') {
+                        let day = parse_number::<i32>(key, item)?;
+                        if day == 0 || !(-31..=31).contains(&day) {
+                            return Err(invalid(&format!("BYMONTHDAY 超出范围: {}", day)));
+                        }
+                        by_month_day.push(day);
+                    }
+                }
+                "BYMONTH" => {
+                    for item in value.split(',') {
+                        let month = parse_number::<u32>(key, item)?;
+                        if !(1..=12).contains(&month) {
+                            return Err(invalid(&format!("BYMONTH 超出范围: {}", month)));
+                        }
+                        by_month.push(month);
+                    }
+                }
+                "BYSETPOS" => {
+                    for item in value.split(',') {
+                        let pos = parse_number::<i32>(key, item)?;
+                        if pos == 0 {
+                            return Err(invalid("BYSETPOS 不能为 0"));
+                        }
+                        by_set_pos.push(pos);
+                    }
+                }
+                "WKST" => {}
+                _ => return Err(invalid(&format!("不支持的属性 \"{}\"", key))),
+            }
+        }
+
+        if count.is_some() && until.is_some() {
+            return Err(invalid("COUNT 与 UNTIL 不能同时使用"));
+        }
+
+        Ok(RRule {
+            freq: freq.ok_or_else(|| invalid("缺少 FREQ"))?,
+            interval,
+            count,
+            until,
+            by_day,
+            by_month_day,
+            by_month,
+            by_set_pos,
+        })
+    }
+
+    /// 按时间顺序展开发生日期
+    ///
+    /// `dtstart` 为规则起点（本身也按规则过滤），`exdates` 中的日期会被剔除但仍计入 `COUNT`。
+    /// 只返回晚于 `after`（若提供）的日期，最多 `limit` 个。
+    pub fn occurrences(
+        &self,
+        dtstart: NaiveDate,
+        exdates: &[NaiveDate],
+        after: Option<NaiveDate>,
+        limit: usize,
+    ) -> Vec<NaiveDate> {
+        let mut result = Vec::new();
+        if limit == 0 {
+            return result;
+        }
+
+        let mut emitted = 0u32;
+        for period in 0..MAX_PERIODS {
+            let Some(candidates) = self.expand_period(dtstart, period) else { break };
+
+            for date in candidates {
+                if date < dtstart {
+                    continue;
+                }
+                if let Some(until) = self.until {
+                    if date > until {
+                        return result;
+                    }
+                }
+                if let Some(count) = self.count {
+                    if emitted >= count {
+                        return result;
+                    }
+                }
+                emitted += 1;
+
+                if exdates.contains(&date) || after.is_some_and(|a| date <= a) {
+                    continue;
+                }
+                result.push(date);
+                if result.len() >= limit {
+                    return result;
+                }
+            }
+        }
+        result
+    }
+
+    /// 展开第 `period` 个周期内的候选日期（已排序、已应用 BYSETPOS）
+    ///
+    /// 周期起点超出 chrono 可表示范围时返回 `None`。
+    fn expand_period(&self, dtstart: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+
+        let mut dates: Vec<NaiveDate> = match self.freq {
+            Frequency::Daily => {
+                let day = dtstart.checked_add_days(Days::new(step as u64))?;
+                let matches = self.month_allowed(day)
+                    && (self.by_month_day.is_empty() || month_day_matches(day, &self.by_month_day))
+                    && (self.by_day.is_empty() || self.by_day.iter().any(|(_, wd)| *wd == day.weekday()));
+                if matches { vec![day] } else { Vec::new() }
+            }
+            Frequency::Weekly => {
+                let week_start = dtstart
+                    .checked_sub_days(Days::new(dtstart.weekday().num_days_from_monday() as u64))?
+                    .checked_add_days(Days::new(7 * step as u64))?;
+                let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![dtstart.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, wd)| *wd).collect()
+                };
+                (0..7)
+                    .filter_map(|i| week_start.checked_add_days(Days::new(i)))
+                    .filter(|d| weekdays.contains(&d.weekday()) && self.month_allowed(*d))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let first = first_of_month(dtstart).checked_add_months(Months::new(step))?;
+                if self.month_allowed(first) {
+                    self.expand_month(dtstart, first)
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Yearly => {
+                let year = dtstart.year().checked_add(step as i32)?;
+                let jan1 = NaiveDate::from_ymd_opt(year, 1, 1)?;
+                if !self.by_day.is_empty() && self.by_month.is_empty() && self.by_month_day.is_empty() {
+                    // 全年范围内按序号匹配星期（如 FREQ=YEARLY;BYDAY=20MO）
+                    let days_in_year = if NaiveDate::from_ymd_opt(year, 2, 29).is_some() { 366 } else { 365 };
+                    let all: Vec<NaiveDate> = (0..days_in_year)
+                        .filter_map(|i| jan1.checked_add_days(Days::new(i)))
+                        .collect();
+                    filter_by_day(&all, &self.by_day)
+                } else {
+                    // 没有 BYMONTH 时：有 BYMONTHDAY 则应用到全年每个月，否则沿用起始月份
+                    let months = if !self.by_month.is_empty() {
+                        self.by_month.clone()
+                    } else if !self.by_month_day.is_empty() {
+                        (1..=12).collect()
+                    } else {
+                        vec![dtstart.month()]
+                    };
+                    months
+                        .iter()
+                        .filter_map(|m| NaiveDate::from_ymd_opt(year, *m, 1))
+                        .flat_map(|first| self.expand_month(dtstart, first))
+                        .collect()
+                }
+            }
+        };
+
+        dates.sort();
+        dates.dedup();
+        Some(apply_set_pos(dates, &self.by_set_pos))
+    }
+
+    /// 展开某个月内的候选日期
+    fn expand_month(&self, dtstart: NaiveDate, first: NaiveDate) -> Vec<NaiveDate> {
+        let month_days = days_of_month(first);
+        match (self.by_month_day.is_empty(), self.by_day.is_empty()) {
+            // 没有 BYxxx：沿用起始日的日号，当月不存在则跳过（RFC 5545 行为）
+            (true, true) => month_days.into_iter().filter(|d| d.day() == dtstart.day()).collect(),
+            (false, true) => month_days.into_iter().filter(|d| month_day_matches(*d, &self.by_month_day)).collect(),
+            (true, false) => filter_by_day(&month_days, &self.by_day),
+            (false, false) => filter_by_day(&month_days, &self.by_day)
+                .into_iter()
+                .filter(|d| month_day_matches(*d, &self.by_month_day))
+                .collect(),
+        }
+    }
+
+    fn month_allowed(&self, date: NaiveDate) -> bool {
+        self.by_month.is_empty() || self.by_month.contains(&date.month())
+    }
+}
+
+/// 校验并规范化 RRULE 字符串（去掉 `RRULE:` 前缀与空白，转为大写）
+pub fn normalize(rule: &str) -> String {
+    let upper = rule.trim().to_uppercase();
+    upper.strip_prefix("RRULE:").unwrap_or(&upper).trim().to_string()
+}
+
+/// 解析 EXDATE 日期列表（`YYYY-MM-DD` 或 `YYYYMMDD`）
+pub fn parse_exdates(items: &[String]) -> AppResult<Vec<NaiveDate>> {
+    items
+        .iter()
+        .map(|s| parse_date(s.trim()).ok_or_else(|| invalid(&format!("EXDATE 日期无效 \"{}\"", s))))
+        .collect()
+}
+
+fn invalid(msg: &str) -> AppError {
+    AppError::Business(format!("重复规则无效：{}", msg))
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> AppResult<T> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| invalid(&format!("{} 的值 \"{}\" 不是有效数字", key, value)))
+}
+
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(s, "%Y%m%d"))
+        .ok()
+}
+
+fn parse_until(value: &str) -> AppResult<NaiveDate> {
+    let date_part = value.split('T').next().unwrap_or(value);
+    parse_date(date_part).ok_or_else(|| invalid(&format!("UNTIL 日期无效 \"{}\"", value)))
+}
+
+fn parse_by_day(item: &str) -> AppResult<(Option<i32>, Weekday)> {
+    let item = item.trim();
+    if item.len() < 2 {
+        return Err(invalid(&format!("BYDAY 的值 \"{}\" 无效", item)));
+    }
+    let (ordinal, code) = item.split_at(item.len() - 2);
+    let weekday = match code {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(invalid(&format!("BYDAY 的值 \"{}\" 无效", item))),
+    };
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        let n = parse_number::<i32>("BYDAY", ordinal)?;
+        if n == 0 || !(-53..=53).contains(&n) {
+            return Err(invalid(&format!("BYDAY 序号超出范围: {}", n)));
+        }
+        Some(n)
+    };
+    Ok((ordinal, weekday))
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn days_of_month(first: NaiveDate) -> Vec<NaiveDate> {
+    first.iter_days().take_while(|d| d.month() == first.month()).collect()
+}
+
+fn month_day_matches(date: NaiveDate, by_month_day: &[i32]) -> bool {
+    let last = days_of_month(first_of_month(date)).len() as i32;
+    let day = date.day() as i32;
+    by_month_day.iter().any(|&md| if md > 0 { md == day } else { last + md + 1 == day })
+}
+
+/// 在已排序的候选集合中按 BYDAY（含序号）筛选
+fn filter_by_day(dates: &[NaiveDate], by_day: &[(Option<i32>, Weekday)]) -> Vec<NaiveDate> {
+    let mut result = Vec::new();
+    for (ordinal, weekday) in by_day {
+        let matching: Vec<NaiveDate> = dates.iter().copied().filter(|d| d.weekday() == *weekday).collect();
+        match ordinal {
+            None => result.extend(matching),
+            Some(n) => {
+                let idx = if *n > 0 { *n - 1 } else { matching.len() as i32 + *n };
+                if idx >= 0 {
+                    if let Some(d) = matching.get(idx as usize) {
+                        result.push(*d);
+                    }
+                }
+            }
+        }
+    }
+    result.sort();
+    result.dedup();
+    result
+}
+
+/// 按 BYSETPOS 从周期内的候选集合中取指定位置
+fn apply_set_pos(dates: Vec<NaiveDate>, by_set_pos: &[i32]) -> Vec<NaiveDate> {
+    if by_set_pos.is_empty() {
+        return dates;
+    }
+    let len = dates.len() as i32;
+    let mut picked: Vec<NaiveDate> = by_set_pos
+        .iter()
+        .filter_map(|&pos| {
+            let idx = if pos > 0 { pos - 1 } else { len + pos };
+            if idx >= 0 { dates.get(idx as usize).copied() } else { None }
+        })
+        .collect();
+    picked.sort();
+    picked.dedup();
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn dates(list: &[&str]) -> Vec<NaiveDate> {
+        list.iter().map(|s| d(s)).collect()
+    }
+
+    fn expand(rule: &str, dtstart: &str, limit: usize) -> Vec<NaiveDate> {
+        RRule::parse(rule).unwrap().occurrences(d(dtstart), &[], None, limit)
+    }
+
+    #[test]
+    fn parse_rule() {
+        let rule = RRule::parse("rrule:freq=weekly;interval=2;byday=MO,-1FR;wkst=SU").unwrap();
+        assert_eq!(rule.freq, Frequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![(None, Weekday::Mon), (Some(-1), Weekday::Fri)]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(RRule::parse("").is_err());
+        assert!(RRule::parse("INTERVAL=2").is_err());
+        assert!(RRule::parse("FREQ=HOURLY").is_err());
+        assert!(RRule::parse("FREQ=DAILY;INTERVAL=0").is_err());
+        assert!(RRule::parse("FREQ=DAILY;COUNT=3;UNTIL=20270101").is_err());
+        assert!(RRule::parse("FREQ=MONTHLY;BYMONTHDAY=32").is_err());
+        assert!(RRule::parse("FREQ=MONTHLY;BYDAY=XX").is_err());
+        assert!(RRule::parse("FREQ=MONTHLY;BYSETPOS=0").is_err());
+    }
+
+    #[test]
+    fn every_two_weeks_on_monday_and_thursday() {
+        // 2026-01-05 是周一
+        assert_eq!(
+            expand("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH", "2026-01-05", 5),
+            dates(&["2026-01-05", "2026-01-08", "2026-01-19", "2026-01-22", "2026-02-02"]),
+        );
+    }
+
+    #[test]
+    fn last_weekday_of_month() {
+        // 2026-01-31、2026-02-28 都是周六
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1", "2026-01-01", 4),
+            dates(&["2026-01-30", "2026-02-27", "2026-03-31", "2026-04-30"]),
+        );
+    }
+
+    #[test]
+    fn until_is_inclusive() {
+        let expected = dates(&["2026-12-30", "2026-12-31", "2027-01-01"]);
+        assert_eq!(expand("FREQ=DAILY;UNTIL=20270101", "2026-12-30", 10), expected);
+        assert_eq!(expand("FREQ=DAILY;UNTIL=20270101T235959Z", "2026-12-30", 10), expected);
+    }
+
+    #[test]
+    fn count_limits_occurrences() {
+        let all = expand("FREQ=DAILY;COUNT=10", "2026-01-01", 100);
+        assert_eq!(all.len(), 10);
+        assert_eq!(all.last(), Some(&d("2026-01-10")));
+    }
+
+    #[test]
+    fn exdate_still_counts_toward_count() {
+        let rule = RRule::parse("FREQ=DAILY;COUNT=3").unwrap();
+        assert_eq!(
+            rule.occurrences(d("2026-01-01"), &dates(&["2026-01-02"]), None, 10),
+            dates(&["2026-01-01", "2026-01-03"]),
+        );
+    }
+
+    #[test]
+    fn after_skips_earlier_occurrences_but_keeps_count() {
+        let rule = RRule::parse("FREQ=WEEKLY;COUNT=4").unwrap();
+        assert_eq!(
+            rule.occurrences(d("2026-01-05"), &[], Some(d("2026-01-12")), 10),
+            dates(&["2026-01-19", "2026-01-26"]),
+        );
+    }
+
+    #[test]
+    fn monthly_skips_months_without_the_day() {
+        assert_eq!(
+            expand("FREQ=MONTHLY", "2026-01-31", 3),
+            dates(&["2026-01-31", "2026-03-31", "2026-05-31"]),
+        );
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYMONTHDAY=-1", "2026-01-15", 3),
+            dates(&["2026-01-31", "2026-02-28", "2026-03-31"]),
+        );
+    }
+
+    #[test]
+    fn yearly_by_month_day_applies_to_every_month() {
+        assert_eq!(
+            expand("FREQ=YEARLY;BYMONTHDAY=1", "2026-10-15", 4),
+            dates(&["2026-11-01", "2026-12-01", "2027-01-01", "2027-02-01"]),
+        );
+        assert_eq!(
+            expand("FREQ=YEARLY;BYMONTH=6;BYMONTHDAY=1", "2026-10-15", 2),
+            dates(&["2027-06-01", "2028-06-01"]),
+        );
+    }
+
+    #[test]
+    fn yearly_on_leap_day() {
+        assert_eq!(
+            expand("FREQ=YEARLY", "2024-02-29", 2),
+            dates(&["2024-02-29", "2028-02-29"]),
+        );
+    }
+
+    #[test]
+    fn impossible_rule_stops_at_max_periods() {
+        assert!(expand("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30", "2026-01-01", 1).is_empty());
+        assert!(expand("FREQ=DAILY;BYMONTH=2;BYMONTHDAY=30", "2026-01-01", 1).is_empty());
+        // 第 MAX_PERIODS 个周期之后的日期不会再展开
+        let last = expand("FREQ=DAILY", "2026-01-01", usize::MAX);
+        assert_eq!(last.len(), MAX_PERIODS as usize);
+    }
+
+    #[test]
+    fn parse_exdate_formats() {
+        let items = vec!["2026-01-02".to_string(), " 20260103 ".to_string()];
+        assert_eq!(parse_exdates(&items).unwrap(), dates(&["2026-01-02", "2026-01-03"]));
+        assert!(parse_exdates(&["2026-02-30".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! 后台提醒调度器
+//!
+//! 在 Tauri 异步运行时中定时轮询数据库，发送到期的任务提醒和每日写作提醒，
+//! 并检查计划是否已到自动完成的条件。
+//! 通知由 Rust 端直接发出，主窗口隐藏到托盘时也能正常提醒。
+
+use std::time::Duration;
+use chrono::{NaiveDateTime, NaiveTime};
+use rusqlite::Connection;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::plan_cmd::emit_plan_completed;
+use crate::commands::task_cmd::DbState;
+use crate::db::{plan_dao, settings_dao, task_dao};
+use crate::errors::AppResult;
+use crate::models::task::DueReminder;
+
+/// 轮询间隔（秒）
+const TICK_SECONDS: u64 = 30;
+
+/// 每日写作提醒错过后仍会补发的时间窗口（分钟）
+const WRITING_REMINDER_GRACE_MINUTES: i64 = 60;
+
+/// 记录每日写作提醒最近发送日期的设置键
+const LAST_WRITING_REMINDER_KEY: &str = "writing_reminder.last_remind_date";
+const LAST_WRITING_FOLLOW_UP_KEY: &str = "writing_reminder.last_follow_up_date";
+
+/// 通知正文中写作提示的最大字符数
+const PROMPT_SNIPPET_CHARS: usize = 40;
+
+/// 启动调度器（在 `setup` 中调用一次）
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(TICK_SECONDS));
+        loop {
+            interval.tick().await;
+            tick(&app);
+        }
+    });
+}
+
+/// 单次轮询：收集到期的提醒和自动完成的计划，发送通知和事件
+///
+/// 数据库锁只在收集阶段持有，发送通知时已释放。
+fn tick(app: &AppHandle) {
+    let now = chrono::Local::now().naive_local();
+
+    let (notifications, completed_plans) = {
+        let db = app.state::<DbState>();
+        let conn = match db.lock() {
+            Ok(conn) => conn,
+            Err(e) => {
+                log::error!("数据库锁不可用，跳过本轮提醒: {}", e);
+                return;
+            }
+        };
+        let completed_plans = plan_dao::auto_complete_plans(&conn, now).unwrap_or_else(|e| {
+            log::error!("检查计划完成失败: {}", e);
+            Vec::new()
+        });
+        (collect_notifications(&conn, now), completed_plans)
+    };
+
+    for (title, body) in &notifications {
+        notify(app, title, body);
+    }
+    for plan in &completed_plans {
+        notify(app, "写作计划完成", &format!("《{}》已完成，共写了 {} 天", plan.plan_name, plan.completed_days));
+    }
+    emit_plan_completed(app, &completed_plans);
+}
+
+/// 收集本轮需要发送的通知 (标题, 正文)
+fn collect_notifications(conn: &Connection, now: NaiveDateTime) -> Vec<(String, String)> {
+    let mut notifications = Vec::new();
+
+    match task_dao::take_due_reminders(conn, now) {
+        Ok(reminders) => notifications.extend(
+            reminders.iter().map(|r| ("WriteDo - 任务提醒".to_string(), task_reminder_body(r))),
+        ),
+        Err(e) => log::error!("读取任务提醒失败: {}", e),
+    }
+
+    match take_writing_reminders(conn, now) {
+        Ok(items) => notifications.extend(items),
+        Err(e) => log::error!("读取写作提醒失败: {}", e),
+    }
+
+    notifications
+}
+
+/// 任务提醒的通知正文
+fn task_reminder_body(reminder: &DueReminder) -> String {
+    match reminder.due_time {
+        Some(ref time) => format!("{}（截止 {} {}）", reminder.task_title, reminder.due_date, time),
+        None => format!("{}（{} 截止）", reminder.task_title, reminder.due_date),
+    }
+}
+
+/// 每日写作提醒：到点提醒今日计划题目，二次提醒时间仍未写作则提示连续记录即将中断
+fn take_writing_reminders(conn: &Connection, now: NaiveDateTime) -> AppResult<Vec<(String, String)>> {
+    let settings = settings_dao::get_writing_reminder_settings(conn)?;
+    let mut notifications = Vec::new();
+    if !settings.enabled {
+        return Ok(notifications);
+    }
+
+    let Some(today_task) = plan_dao::get_today_writing_task(conn)? else {
+        return Ok(notifications);
+    };
+
+    if take_daily_trigger(conn, &settings.remind_at, LAST_WRITING_REMINDER_KEY, now)? && !today_task.is_completed {
+        notifications.push((
+            format!("今日写作：{}", today_task.title),
+            format!("《{}》第 {} 天 · {}", today_task.plan_name, today_task.day_number, snippet(&today_task.prompt)),
+        ));
+    }
+
+    if settings.follow_up_enabled
+        && take_daily_trigger(conn, &settings.follow_up_at, LAST_WRITING_FOLLOW_UP_KEY, now)?
+        && !today_task.is_completed
+    {
+        notifications.push((
+            "连续写作即将中断".to_string(),
+            format!("今天的「{}」还没有动笔，写几句也算数", today_task.title),
+        ));
+    }
+
+    Ok(notifications)
+}
+
+/// 判断每天固定时刻的提醒是否应在本轮触发，并记录为今天已处理
+///
+/// 已过触发时刻但超出补发窗口时只记录、不提醒。
+fn take_daily_trigger(conn: &Connection, time: &str, key: &str, now: NaiveDateTime) -> AppResult<bool> {
+    let Ok(time) = NaiveTime::parse_from_str(time, "%H:%M") else { return Ok(false) };
+    let trigger = now.date().and_time(time);
+    if trigger > now {
+        return Ok(false);
+    }
+
+    let today = now.date().to_string();
+    if settings_dao::get_setting(conn, key)?.as_deref() == Some(today.as_str()) {
+        return Ok(false);
+    }
+    settings_dao::set_setting(conn, key, &today)?;
+
+    Ok(now - trigger <= chrono::Duration::minutes(WRITING_REMINDER_GRACE_MINUTES))
+}
+
+/// 截取写作提示的开头作为通知摘要
+fn snippet(text: &str) -> String {
+    let mut chars = text.chars();
+    let head: String = chars.by_ref().take(PROMPT_SNIPPET_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}…", head)
+    } else {
+        head
+    }
+}
+
+/// 发送系统通知
+fn notify(app: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("通知发送失败: {}", e);
+    }
+}
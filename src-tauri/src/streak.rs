@@ -0,0 +1,338 @@
+//! 连续写作天数计算
+//!
+//! 纯函数，不访问数据库也不读取系统时间，「今天」由调用方传入。每一天属于以下三种之一：
+//! - 写作日：计入连续天数
+//! - 跳过日：没有写作的休息日或冻结日，既不计入也不中断连续
+//! - 缺勤日：中断连续
+//!
+//! 今天还没有写作时视为尚未结束，不中断当前连续；晚于今天的日期被忽略。
+
+use std::collections::{BTreeSet, HashSet};
+use chrono::{Datelike, NaiveDate};
+use crate::models::settings::StreakSettings;
+use crate::models::streak::*;
+
+/// 不中断连续的规则
+#[derive(Debug, Clone, Default)]
+pub struct StreakRules {
+    /// 每周固定的休息日（1 = 周一 … 7 = 周日）
+    pub rest_weekdays: HashSet<u32>,
+    /// 冻结日
+    pub freeze_dates: HashSet<NaiveDate>,
+}
+
+impl StreakRules {
+    /// 从设置构造规则，忽略格式无效的冻结日期
+    pub fn from_settings(settings: &StreakSettings) -> Self {
+        StreakRules {
+            rest_weekdays: settings.rest_weekdays.iter().copied().collect(),
+            freeze_dates: settings.freeze_dates.iter()
+                .filter_map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+                .collect(),
+        }
+    }
+
+    /// 没有写作时是否跳过这一天
+    fn is_skipped(&self, day: NaiveDate) -> bool {
+        self.rest_weekdays.contains(&day.weekday().number_from_monday())
+            || self.freeze_dates.contains(&day)
+    }
+}
+
+/// 根据写作日期（可重复、无需排序）计算连续写作统计
+pub fn summarize(dates: &[NaiveDate], today: NaiveDate, rules: &StreakRules) -> StreakSummary {
+    let written: BTreeSet<NaiveDate> = dates.iter().copied().filter(|d| *d <= today).collect();
+    let today_written = written.contains(&today);
+    let mut summary = StreakSummary { today: Some(today), today_written, ..Default::default() };
+
+    let Some(&first) = written.first() else {
+        return summary;
+    };
+    // 今天没写作时，最后一个已经结束的日子是昨天
+    let last = if today_written { today } else { today - chrono::Duration::days(1) };
+
+    let mut runs: Vec<StreakRange> = Vec::new();
+    let mut run: Option<StreakRange> = None;
+    let mut gap: Option<StreakGap> = None;
+
+    for day in first.iter_days().take_while(|d| *d <= last) {
+        if written.contains(&day) {
+            if let Some(g) = gap.take() {
+                summary.gaps.push(g);
+            }
+            match run.as_mut() {
+                Some(r) => {
+                    r.end = day;
+                    r.days += 1;
+                }
+                None => run = Some(StreakRange { start: day, end: day, days: 1 }),
+            }
+        } else if rules.is_skipped(day) {
+            continue;
+        } else {
+            if let Some(r) = run.take() {
+                runs.push(r);
+            }
+            match gap.as_mut() {
+                Some(g) => {
+                    g.end = day;
+                    g.days += 1;
+                }
+                None => gap = Some(StreakGap { start: day, end: day, days: 1 }),
+            }
+        }
+    }
+    if let Some(g) = gap {
+        summary.gaps.push(g);
+    }
+
+    // 扫描到最后仍未中断的一段就是当前连续
+    if let Some(r) = run {
+        summary.current_streak = r.days;
+        summary.current = Some(r.clone());
+        runs.push(r);
+    }
+
+    // max_by_key 在相等时返回最后一个，即最近的一段
+    summary.longest = runs.into_iter().max_by_key(|r| r.days);
+    summary.max_streak = summary.longest.as_ref().map(|r| r.days).unwrap_or(0);
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn dates(list: &[&str]) -> Vec<NaiveDate> {
+        list.iter().map(|s| d(s)).collect()
+    }
+
+    fn range(start: &str, end: &str, days: i32) -> StreakRange {
+        StreakRange { start: d(start), end: d(end), days }
+    }
+
+    fn gap(start: &str, end: &str, days: i32) -> StreakGap {
+        StreakGap { start: d(start), end: d(end), days }
+    }
+
+    fn rest_on(weekdays: &[u32]) -> StreakRules {
+        StreakRules { rest_weekdays: weekdays.iter().copied().collect(), ..Default::default() }
+    }
+
+    fn frozen_on(list: &[&str]) -> StreakRules {
+        StreakRules { freeze_dates: dates(list).into_iter().collect(), ..Default::default() }
+    }
+
+    #[test]
+    fn empty_history() {
+        let s = summarize(&[], d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.current_streak, 0);
+        assert_eq!(s.max_streak, 0);
+        assert_eq!(s.current, None);
+        assert_eq!(s.longest, None);
+        assert!(s.gaps.is_empty());
+        assert!(!s.today_written);
+        assert_eq!(s.today, Some(d("2024-03-10")));
+    }
+
+    #[test]
+    fn only_today() {
+        let s = summarize(&dates(&["2024-03-10"]), d("2024-03-10"), &StreakRules::default());
+        assert!(s.today_written);
+        assert_eq!(s.current_streak, 1);
+        assert_eq!(s.current, Some(range("2024-03-10", "2024-03-10", 1)));
+        assert_eq!(s.max_streak, 1);
+    }
+
+    #[test]
+    fn streak_ending_today() {
+        let s = summarize(&dates(&["2024-03-08", "2024-03-09", "2024-03-10"]), d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.current_streak, 3);
+        assert_eq!(s.current, Some(range("2024-03-08", "2024-03-10", 3)));
+        assert!(s.gaps.is_empty());
+    }
+
+    #[test]
+    fn today_not_yet_written_keeps_streak() {
+        let s = summarize(&dates(&["2024-03-08", "2024-03-09"]), d("2024-03-10"), &StreakRules::default());
+        assert!(!s.today_written);
+        assert_eq!(s.current_streak, 2);
+        assert_eq!(s.current, Some(range("2024-03-08", "2024-03-09", 2)));
+        assert!(s.gaps.is_empty());
+    }
+
+    #[test]
+    fn missed_yesterday_breaks_streak() {
+        let s = summarize(&dates(&["2024-03-07", "2024-03-08"]), d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.current_streak, 0);
+        assert_eq!(s.current, None);
+        assert_eq!(s.max_streak, 2);
+        assert_eq!(s.gaps, vec![gap("2024-03-09", "2024-03-09", 1)]);
+    }
+
+    #[test]
+    fn last_writing_weeks_ago_has_no_current_streak() {
+        let s = summarize(&dates(&["2024-01-01", "2024-01-02", "2024-01-03"]), d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.current_streak, 0);
+        assert_eq!(s.max_streak, 3);
+        assert_eq!(s.longest, Some(range("2024-01-01", "2024-01-03", 3)));
+        assert_eq!(s.gaps, vec![gap("2024-01-04", "2024-03-09", 66)]);
+    }
+
+    #[test]
+    fn single_old_day() {
+        let s = summarize(&dates(&["2024-03-01"]), d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.current_streak, 0);
+        assert_eq!(s.max_streak, 1);
+    }
+
+    #[test]
+    fn longest_streak_with_range() {
+        let s = summarize(
+            &dates(&["2024-03-01", "2024-03-02", "2024-03-03", "2024-03-04", "2024-03-06", "2024-03-09", "2024-03-10"]),
+            d("2024-03-10"),
+            &StreakRules::default(),
+        );
+        assert_eq!(s.current_streak, 2);
+        assert_eq!(s.max_streak, 4);
+        assert_eq!(s.longest, Some(range("2024-03-01", "2024-03-04", 4)));
+        assert_eq!(s.gaps, vec![gap("2024-03-05", "2024-03-05", 1), gap("2024-03-07", "2024-03-08", 2)]);
+    }
+
+    #[test]
+    fn longest_tie_prefers_most_recent() {
+        let s = summarize(&dates(&["2024-03-01", "2024-03-02", "2024-03-05", "2024-03-06"]), d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.longest, Some(range("2024-03-05", "2024-03-06", 2)));
+    }
+
+    #[test]
+    fn current_streak_can_be_longest() {
+        let s = summarize(&dates(&["2024-03-01", "2024-03-05", "2024-03-06", "2024-03-07"]), d("2024-03-07"), &StreakRules::default());
+        assert_eq!(s.current, s.longest);
+        assert_eq!(s.max_streak, 3);
+    }
+
+    #[test]
+    fn duplicates_and_unsorted_input() {
+        let s = summarize(&dates(&["2024-03-10", "2024-03-08", "2024-03-09", "2024-03-09", "2024-03-08"]), d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.current_streak, 3);
+        assert_eq!(s.max_streak, 3);
+    }
+
+    #[test]
+    fn future_dates_are_ignored() {
+        let s = summarize(&dates(&["2024-03-09", "2024-03-11", "2024-03-12"]), d("2024-03-10"), &StreakRules::default());
+        assert_eq!(s.current_streak, 1);
+        assert_eq!(s.max_streak, 1);
+        assert!(!s.today_written);
+    }
+
+    #[test]
+    fn month_and_year_boundaries() {
+        let s = summarize(&dates(&["2023-12-30", "2023-12-31", "2024-01-01"]), d("2024-01-01"), &StreakRules::default());
+        assert_eq!(s.current, Some(range("2023-12-30", "2024-01-01", 3)));
+
+        // 闰年 2 月 29 日
+        let s = summarize(&dates(&["2024-02-28", "2024-02-29", "2024-03-01"]), d("2024-03-01"), &StreakRules::default());
+        assert_eq!(s.current_streak, 3);
+
+        let s = summarize(&dates(&["2023-02-28", "2023-03-01"]), d("2023-03-01"), &StreakRules::default());
+        assert_eq!(s.current_streak, 2);
+    }
+
+    #[test]
+    fn rest_weekday_does_not_break_streak() {
+        // 2024-03-09 周六、2024-03-10 周日
+        let s = summarize(&dates(&["2024-03-07", "2024-03-08", "2024-03-11"]), d("2024-03-11"), &rest_on(&[6, 7]));
+        assert_eq!(s.current_streak, 3);
+        assert_eq!(s.current, Some(range("2024-03-07", "2024-03-11", 3)));
+        assert!(s.gaps.is_empty());
+    }
+
+    #[test]
+    fn writing_on_rest_day_still_counts() {
+        let s = summarize(&dates(&["2024-03-08", "2024-03-09", "2024-03-11"]), d("2024-03-11"), &rest_on(&[6, 7]));
+        assert_eq!(s.current_streak, 3);
+    }
+
+    #[test]
+    fn trailing_rest_days_keep_current_streak() {
+        // 周五写作，今天周日未写，周六休息
+        let s = summarize(&dates(&["2024-03-07", "2024-03-08"]), d("2024-03-10"), &rest_on(&[6]));
+        assert_eq!(s.current_streak, 2);
+        assert_eq!(s.current, Some(range("2024-03-07", "2024-03-08", 2)));
+    }
+
+    #[test]
+    fn rest_days_do_not_count_as_gap_days() {
+        // 周四写作，周五缺勤，周末休息，周一缺勤，周二写作
+        let s = summarize(&dates(&["2024-03-07", "2024-03-12"]), d("2024-03-12"), &rest_on(&[6, 7]));
+        assert_eq!(s.gaps, vec![gap("2024-03-08", "2024-03-11", 2)]);
+        assert_eq!(s.current_streak, 1);
+    }
+
+    #[test]
+    fn every_day_rest_never_breaks() {
+        let s = summarize(&dates(&["2024-03-01", "2024-03-10"]), d("2024-03-10"), &rest_on(&[1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(s.current, Some(range("2024-03-01", "2024-03-10", 2)));
+        assert!(s.gaps.is_empty());
+    }
+
+    #[test]
+    fn freeze_bridges_missed_day() {
+        let s = summarize(&dates(&["2024-03-07", "2024-03-09", "2024-03-10"]), d("2024-03-10"), &frozen_on(&["2024-03-08"]));
+        assert_eq!(s.current_streak, 3);
+        assert_eq!(s.current, Some(range("2024-03-07", "2024-03-10", 3)));
+        assert!(s.gaps.is_empty());
+    }
+
+    #[test]
+    fn freeze_covers_only_its_own_day() {
+        let s = summarize(&dates(&["2024-03-06", "2024-03-09"]), d("2024-03-09"), &frozen_on(&["2024-03-07"]));
+        assert_eq!(s.current_streak, 1);
+        assert_eq!(s.max_streak, 1);
+        assert_eq!(s.gaps, vec![gap("2024-03-08", "2024-03-08", 1)]);
+    }
+
+    #[test]
+    fn freeze_on_yesterday_keeps_streak_until_today() {
+        let s = summarize(&dates(&["2024-03-07", "2024-03-08"]), d("2024-03-10"), &frozen_on(&["2024-03-09"]));
+        assert_eq!(s.current_streak, 2);
+    }
+
+    #[test]
+    fn freeze_before_first_writing_is_irrelevant() {
+        let s = summarize(&dates(&["2024-03-10"]), d("2024-03-10"), &frozen_on(&["2024-03-01"]));
+        assert_eq!(s.current_streak, 1);
+        assert!(s.gaps.is_empty());
+    }
+
+    #[test]
+    fn rest_days_and_freezes_combine() {
+        // 周四写作，周五冻结，周末休息，周一写作
+        let rules = StreakRules {
+            rest_weekdays: [6, 7].into_iter().collect(),
+            freeze_dates: dates(&["2024-03-08"]).into_iter().collect(),
+        };
+        let s = summarize(&dates(&["2024-03-07", "2024-03-11"]), d("2024-03-11"), &rules);
+        assert_eq!(s.current, Some(range("2024-03-07", "2024-03-11", 2)));
+    }
+
+    #[test]
+    fn rules_from_settings_skip_invalid_dates() {
+        let settings = StreakSettings {
+            rest_weekdays: vec![7],
+            freeze_dates: vec!["2024-03-08".to_string(), "not-a-date".to_string()],
+            ..Default::default()
+        };
+        let rules = StreakRules::from_settings(&settings);
+        assert_eq!(rules.freeze_dates.len(), 1);
+        assert!(rules.is_skipped(d("2024-03-10")));
+        assert!(rules.is_skipped(d("2024-03-08")));
+        assert!(!rules.is_skipped(d("2024-03-09")));
+    }
+}
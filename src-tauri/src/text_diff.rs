@@ -0,0 +1,271 @@
+//! 文本差异比较（Myers 算法）
+//!
+//! 先把文本切分为词元再逐词元比较：
+//! - 按行：每行（含换行符）为一个词元
+//! - 按字：中日韩文字逐字切分，拉丁字母和数字连续的部分作为一个词，空白和标点单独成词元
+//!
+//! 编辑距离超过 [`MAX_EDIT_DISTANCE`] 时不再细分，整体视为删除旧文本、插入新文本。
+
+use crate::models::revision::{DiffGranularity, DiffOp, DiffSegment};
+use crate::text_metrics::is_cjk;
+
+/// 最大编辑距离（词元数），限制最坏情况下的内存和耗时
+const MAX_EDIT_DISTANCE: usize = 1_000;
+
+/// 比较两段文本，返回合并后的差异片段
+pub fn diff(old: &str, new: &str, granularity: DiffGranularity) -> Vec<DiffSegment> {
+    let a = tokenize(old, granularity);
+    let b = tokenize(new, granularity);
+
+    // 去掉公共前后缀，缩小需要比较的范围
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut ops: Vec<(DiffOp, &str)> = a[..prefix].iter().map(|t| (DiffOp::Equal, *t)).collect();
+    match myers(a_mid, b_mid) {
+        Some(mid) => {
+            let (mut i, mut j) = (0, 0);
+            for op in mid {
+                match op {
+                    DiffOp::Equal => { ops.push((op, a_mid[i])); i += 1; j += 1; }
+                    DiffOp::Delete => { ops.push((op, a_mid[i])); i += 1; }
+                    DiffOp::Insert => { ops.push((op, b_mid[j])); j += 1; }
+                }
+            }
+        }
+        None => {
+            ops.extend(a_mid.iter().map(|t| (DiffOp::Delete, *t)));
+            ops.extend(b_mid.iter().map(|t| (DiffOp::Insert, *t)));
+        }
+    }
+    ops.extend(a[a.len() - suffix..].iter().map(|t| (DiffOp::Equal, *t)));
+
+    merge(ops)
+}
+
+/// 切分词元
+fn tokenize(text: &str, granularity: DiffGranularity) -> Vec<&str> {
+    match granularity {
+        DiffGranularity::Line => text.split_inclusive('\n').collect(),
+        DiffGranularity::Char => {
+            let mut tokens = Vec::new();
+            let mut word_start: Option<usize> = None;
+            for (i, c) in text.char_indices() {
+                if is_word_char(c) {
+                    word_start.get_or_insert(i);
+                    continue;
+                }
+                if let Some(start) = word_start.take() {
+                    tokens.push(&text[start..i]);
+                }
+                tokens.push(&text[i..i + c.len_utf8()]);
+            }
+            if let Some(start) = word_start {
+                tokens.push(&text[start..]);
+            }
+            tokens
+        }
+    }
+}
+
+/// 可以连成一个词的字符：字母和数字，但不含中日韩文字（逐字比较）
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() && !is_cjk(c)
+}
+
+/// Myers O(ND) 差异算法，返回逐词元的操作序列；编辑距离超过上限时返回 `None`
+fn myers(a: &[&str], b: &[&str]) -> Option<Vec<DiffOp>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let limit = (a.len() + b.len()).min(MAX_EDIT_DISTANCE) as isize;
+    let offset = limit + 1;
+    let mut v = vec![0isize; (2 * limit + 3) as usize];
+    // trace[d] 保存第 d 步结束时 k ∈ [-d, d] 的 V 值
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=limit {
+        for k in (-d..=d).step_by(2) {
+            let idx = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+
+            if x >= n && y >= m {
+                trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+                return Some(backtrack(&trace, n, m));
+            }
+        }
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+    }
+
+    None
+}
+
+/// 从终点沿 trace 回溯出操作序列
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<DiffOp> {
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for d in (1..trace.len() as isize).rev() {
+        let prev = &trace[(d - 1) as usize];
+        let get = |k: isize| prev[(k + d - 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) { k + 1 } else { k - 1 };
+        let prev_x = get(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            ops.push(DiffOp::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        ops.push(if x == prev_x { DiffOp::Insert } else { DiffOp::Delete });
+        x = prev_x;
+        y = prev_y;
+    }
+    while x > 0 && y > 0 {
+        ops.push(DiffOp::Equal);
+        x -= 1;
+        y -= 1;
+    }
+
+    ops.reverse();
+    ops
+}
+
+/// 合并相邻的同类操作
+fn merge(ops: Vec<(DiffOp, &str)>) -> Vec<DiffSegment> {
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    for (op, text) in ops {
+        match segments.last_mut() {
+            Some(last) if last.op == op => last.text.push_str(text),
+            _ => segments.push(DiffSegment { op, text: text.to_string() }),
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DiffOp::{Delete, Equal, Insert};
+
+    fn ops(segments: &[DiffSegment]) -> Vec<(DiffOp, &str)> {
+        segments.iter().map(|s| (s.op, s.text.as_str())).collect()
+    }
+
+    /// 由差异片段还原出旧文本和新文本
+    fn rebuild(segments: &[DiffSegment]) -> (String, String) {
+        let mut old = String::new();
+        let mut new = String::new();
+        for s in segments {
+            if s.op != Insert {
+                old.push_str(&s.text);
+            }
+            if s.op != Delete {
+                new.push_str(&s.text);
+            }
+        }
+        (old, new)
+    }
+
+    fn char_diff(old: &str, new: &str) -> Vec<DiffSegment> {
+        diff(old, new, DiffGranularity::Char)
+    }
+
+    #[test]
+    fn empty_to_text() {
+        assert_eq!(ops(&char_diff("", "你好 world")), vec![(Insert, "你好 world")]);
+    }
+
+    #[test]
+    fn text_to_empty() {
+        assert_eq!(ops(&char_diff("你好 world", "")), vec![(Delete, "你好 world")]);
+    }
+
+    #[test]
+    fn identical_input() {
+        assert_eq!(ops(&char_diff("今天天气很好。", "今天天气很好。")), vec![(Equal, "今天天气很好。")]);
+        assert!(char_diff("", "").is_empty());
+    }
+
+    #[test]
+    fn cjk_single_character_insert_and_delete() {
+        assert_eq!(
+            ops(&char_diff("今天很好", "今天真很好")),
+            vec![(Equal, "今天"), (Insert, "真"), (Equal, "很好")],
+        );
+        assert_eq!(
+            ops(&char_diff("今天真很好", "今天很好")),
+            vec![(Equal, "今天"), (Delete, "真"), (Equal, "很好")],
+        );
+    }
+
+    #[test]
+    fn latin_words_are_compared_whole() {
+        assert_eq!(
+            ops(&char_diff("the quick fox", "the slow fox")),
+            vec![(Equal, "the "), (Delete, "quick"), (Insert, "slow"), (Equal, " fox")],
+        );
+    }
+
+    #[test]
+    fn latin_word_swap() {
+        let segments = char_diff("red green blue", "blue green red");
+        assert_eq!(rebuild(&segments), ("red green blue".to_string(), "blue green red".to_string()));
+        assert!(segments.iter().any(|s| s.op == Equal && s.text.contains("green")));
+    }
+
+    #[test]
+    fn line_granularity() {
+        assert_eq!(
+            ops(&diff("一\n二\n三\n", "一\n贰\n三\n", DiffGranularity::Line)),
+            vec![(Equal, "一\n"), (Delete, "二\n"), (Insert, "贰\n"), (Equal, "三\n")],
+        );
+    }
+
+    #[test]
+    fn tokenize_splits_cjk_and_keeps_words() {
+        assert_eq!(
+            tokenize("我用Rust写code，don't", DiffGranularity::Char),
+            vec!["我", "用", "Rust", "写", "code", "，", "don", "'", "t"],
+        );
+    }
+
+    #[test]
+    fn large_edit_falls_back_to_replace() {
+        let old: String = (0..MAX_EDIT_DISTANCE).map(|i| format!("a{} ", i)).collect();
+        let new: String = (0..MAX_EDIT_DISTANCE).map(|i| format!("b{} ", i)).collect();
+        let segments = char_diff(&old, &new);
+        assert_eq!(rebuild(&segments), (old, new));
+        assert_eq!(segments.iter().filter(|s| s.op == Delete).count(), 1);
+        assert_eq!(segments.iter().filter(|s| s.op == Insert).count(), 1);
+    }
+
+    #[test]
+    fn diff_always_rebuilds_both_sides() {
+        let cases = [
+            ("春眠不觉晓，处处闻啼鸟。", "春眠不觉晓，夜来风雨声。"),
+            ("abc def ghi", "abc xyz ghi jkl"),
+            ("第一行\n第二行\n", "第零行\n第一行\n第二行\n第三行"),
+            ("x", "y"),
+        ];
+        for (old, new) in cases {
+            for granularity in [DiffGranularity::Char, DiffGranularity::Line] {
+                let segments = diff(old, new, granularity);
+                assert_eq!(rebuild(&segments), (old.to_string(), new.to_string()));
+            }
+        }
+    }
+}
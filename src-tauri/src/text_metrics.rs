@@ -0,0 +1,169 @@
+//! 文本统计（中日韩文字感知）
+//!
+//! 写作字数由后端统一计算，不再信任前端传入的数值。统计规则：
+//! - 中日韩文字逐字计数
+//! - 拉丁字母和数字按词计数，词内的 `'` `’` `-`（如 don't、well-known）不拆分
+//! - 标点符号（含全角标点）单独计数，不计入字数
+//! - 段落为去掉 HTML 标签后的非空行
+//!
+//! 字数 = 中日韩文字数 + 拉丁词数。
+
+use crate::models::writing::TextMetrics;
+
+/// 结束后需要换行的块级标签
+const BLOCK_END_TAGS: &[&str] = &["/p", "/h1", "/h2", "/h3", "/h4", "/h5", "/h6", "/li", "/blockquote", "/pre"];
+
+/// 统计编辑器保存的 HTML 正文
+pub fn analyze_html(content: &str) -> TextMetrics {
+    analyze(&html_to_text(content))
+}
+
+/// 统计纯文本
+pub fn analyze(text: &str) -> TextMetrics {
+    let mut metrics = TextMetrics::default();
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut in_word = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if is_cjk(c) {
+            metrics.cjk_chars += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                metrics.latin_words += 1;
+                in_word = true;
+            }
+        } else if in_word && is_word_joiner(c) && chars.get(i + 1).is_some_and(|n| n.is_alphanumeric() && !is_cjk(*n)) {
+            // 词内连接符，继续当前词
+        } else {
+            if is_punctuation(c) {
+                metrics.punctuation += 1;
+            }
+            in_word = false;
+        }
+    }
+
+    metrics.paragraphs = text.lines().filter(|line| !line.trim().is_empty()).count() as i32;
+    metrics.word_count = metrics.cjk_chars + metrics.latin_words;
+    metrics
+}
+
+/// 编辑器保存的 HTML 转为纯文本：去掉标签，段落和换行标签转为换行符
+pub fn html_to_text(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    let mut tag: Option<String> = None;
+    for c in content.chars() {
+        match (&mut tag, c) {
+            (None, '<') => tag = Some(String::new()),
+            (Some(name), '>') => {
+                let name = name.trim_end_matches('/').trim().to_lowercase();
+                let name = name.split_whitespace().next().unwrap_or("");
+                if name == "br" || BLOCK_END_TAGS.contains(&name) {
+                    text.push('\n');
+                }
+                tag = None;
+            }
+            (Some(name), _) => name.push(c),
+            (None, _) => text.push(c),
+        }
+    }
+
+    text.trim_end_matches('\n')
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// 中日韩统一表意文字、假名和谚文
+pub fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x4E00..=0x9FFF       // CJK 统一表意文字
+        | 0x3400..=0x4DBF     // 扩展 A
+        | 0x20000..=0x2EBEF   // 扩展 B ~ F
+        | 0x30000..=0x3134F   // 扩展 G
+        | 0xF900..=0xFAFF     // 兼容表意文字
+        | 0x3040..=0x30FF     // 平假名、片假名
+        | 0xAC00..=0xD7AF     // 谚文音节
+    )
+}
+
+/// 标点符号：ASCII 标点、通用标点、中日韩标点和全角标点
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || (!c.is_alphanumeric() && !c.is_whitespace() && matches!(c as u32,
+            0x2010..=0x205E       // 通用标点（— … “ ” 等）
+            | 0x3000..=0x303F     // 中日韩标点（。、「」等）
+            | 0xFE30..=0xFE4F     // 中日韩兼容形式
+            | 0xFF00..=0xFFEF     // 全角 ASCII（，！？等）
+        ))
+}
+
+/// 可以出现在拉丁词内部的连接符
+fn is_word_joiner(c: char) -> bool {
+    matches!(c, '\'' | '\u{2019}' | '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 与前端 `src/utils/textMetrics.ts` 共用的字数用例，两端必须得出相同结果
+    const SHARED_CASES: &str = include_str!("../../src/utils/textMetrics.cases.json");
+
+    #[derive(serde::Deserialize)]
+    struct Case {
+        text: String,
+        words: i32,
+    }
+
+    #[test]
+    fn shared_word_count_cases() {
+        let cases: Vec<Case> = serde_json::from_str(SHARED_CASES).unwrap();
+        for case in cases {
+            assert_eq!(analyze(&case.text).word_count, case.words, "{:?}", case.text);
+        }
+    }
+
+    #[test]
+    fn mixed_cjk_and_latin() {
+        let m = analyze("今天学习Rust的所有权，ownership rules很重要！");
+        assert_eq!(m.cjk_chars, 11);
+        assert_eq!(m.latin_words, 3);
+        assert_eq!(m.punctuation, 2);
+        assert_eq!(m.word_count, 14);
+        assert_eq!(m.paragraphs, 1);
+    }
+
+    #[test]
+    fn html_tags_are_not_counted() {
+        let m = analyze_html(r#"<p class="lead">春天<strong>来了</strong></p><p>Spring <em>is</em> here</p>"#);
+        assert_eq!(m.cjk_chars, 4);
+        assert_eq!(m.latin_words, 3);
+        assert_eq!(m.paragraphs, 2);
+    }
+
+    #[test]
+    fn entities_are_decoded() {
+        assert_eq!(html_to_text("<p>a&nbsp;&amp;&nbsp;b &lt;tag&gt; &quot;x&quot; it&#39;s</p>"), "a & b <tag> \"x\" it's");
+        let m = analyze_html("<p>it&#39;s&nbsp;fine&amp;dandy</p>");
+        assert_eq!(m.latin_words, 3);
+        assert_eq!(m.punctuation, 1);
+    }
+
+    #[test]
+    fn line_breaks_and_empty_paragraphs() {
+        assert_eq!(html_to_text("<p>一</p><p></p><p>二<br>三<br/></p>"), "一\n\n二\n三");
+        assert_eq!(analyze_html("<p>一</p><p></p><p>二<br>三</p>").paragraphs, 3);
+        assert_eq!(analyze_html("<h2>标题</h2><ul><li>甲</li><li>乙</li></ul>").paragraphs, 3);
+    }
+
+    #[test]
+    fn empty_input() {
+        let m = analyze_html("<p></p>");
+        assert_eq!(m.word_count, 0);
+        assert_eq!(m.paragraphs, 0);
+    }
+}
@@ -8,25 +8,64 @@
 import { invoke } from '@tauri-apps/api/core';
 import type {
     Task,
+    TaskBuckets,
+    TaskQuery,
+    TaskPage,
+    RollOverTasksRequest,
     CreateTaskRequest,
     UpdateTaskRequest,
+    TaskCompletion,
+    TaskEvent,
+    TaskStats,
+    TaskStatsRequest,
+    PreviewRRuleRequest,
+    TaskReminder,
+    ReminderInput,
+    TaskDetail,
     WritingPlan,
     PlanWithDays,
+    UpdatePlanRequest,
+    CatchUpMode,
+    CatchUpResult,
+    PlanEvent,
     ImportPlanRequest,
     GeneratePlanRequest,
     TodayWritingTask,
     Writing,
     SaveWritingRequest,
+    UpdateWritingRequest,
+    RecomputeMetricsResult,
+    TextMetrics,
+    Draft,
+    SaveDraftRequest,
+    WritingRevision,
+    DiffRevisionsRequest,
+    RevisionDiff,
     WritingStats,
-    HeatmapEntry,
+    Heatmap,
+    HeatmapRequest,
     WritingFilter,
+    WritingReminderSettings,
+    StreakSettings,
+    PlanCompletionSettings,
+    StreakSummary,
+    WritingGoal,
+    SetWritingGoalRequest,
+    GoalProgress,
+    AnalyticsRequest,
+    WritingAnalytics,
+    TaskList,
+    Tag,
+    TagStats,
+    SearchRequest,
+    SearchResult,
 } from '../types';
 
 // ==================== 任务 API ====================
 
-/** 获取所有任务 */
-export async function getTasks(): Promise<Task[]> {
-    return invoke('get_tasks');
+/** 获取所有任务（可按标签过滤） */
+export async function getTasks(tagIds?: number[]): Promise<Task[]> {
+    return invoke('get_tasks', { tagIds });
 }
 
 /** 获取今日待办 */
@@ -34,6 +73,21 @@ export async function getTodayTasks(): Promise<Task[]> {
     return invoke('get_today_tasks');
 }
 
+/** 按条件查询任务（筛选、排序、分页） */
+export async function queryTasks(request?: TaskQuery): Promise<TaskPage> {
+    return invoke('query_tasks', { request });
+}
+
+/** 获取按截止日期分组的任务：逾期、今天、明天、本周、以后、无日期 */
+export async function getTaskBuckets(includeCompleted?: boolean): Promise<TaskBuckets> {
+    return invoke('get_task_buckets', { includeCompleted });
+}
+
+/** 把逾期任务顺延到今天，返回被顺延的任务 */
+export async function rollOverOverdueTasks(request?: RollOverTasksRequest): Promise<Task[]> {
+    return invoke('roll_over_overdue_tasks', { request });
+}
+
 /** 创建任务 */
 export async function createTask(request: CreateTaskRequest): Promise<Task> {
     return invoke('create_task', { request });
@@ -54,6 +108,66 @@ export async function toggleTask(id: number): Promise<Task> {
     return invoke('toggle_task', { id });
 }
 
+/** 获取任务详情（含清单项） */
+export async function getTaskDetail(id: number): Promise<TaskDetail> {
+    return invoke('get_task_detail', { id });
+}
+
+/** 添加清单项 */
+export async function addTaskItem(taskId: number, title: string): Promise<TaskDetail> {
+    return invoke('add_task_item', { request: { task_id: taskId, title } });
+}
+
+/** 更新清单项 */
+export async function updateTaskItem(request: { id: number; title?: string; completed?: boolean }): Promise<TaskDetail> {
+    return invoke('update_task_item', { request });
+}
+
+/** 切换清单项完成状态 */
+export async function toggleTaskItem(id: number): Promise<TaskDetail> {
+    return invoke('toggle_task_item', { id });
+}
+
+/** 删除清单项 */
+export async function deleteTaskItem(id: number): Promise<TaskDetail> {
+    return invoke('delete_task_item', { id });
+}
+
+/** 清单项重新排序 */
+export async function reorderTaskItems(taskId: number, itemIds: number[]): Promise<TaskDetail> {
+    return invoke('reorder_task_items', { request: { task_id: taskId, item_ids: itemIds } });
+}
+
+/** 获取重复任务的完成历史 */
+export async function getTaskCompletions(taskId: number): Promise<TaskCompletion[]> {
+    return invoke('get_task_completions', { taskId });
+}
+
+/** 获取任务的事件历史（创建、完成、重新打开、改期） */
+export async function getTaskEvents(taskId: number): Promise<TaskEvent[]> {
+    return invoke('get_task_events', { taskId });
+}
+
+/** 获取任务完成统计（不传请求时为最近 30 天） */
+export async function getTaskStats(request?: TaskStatsRequest): Promise<TaskStats> {
+    return invoke('get_task_stats', { request });
+}
+
+/** 预览重复规则接下来的发生日期 */
+export async function previewRRule(request: PreviewRRuleRequest): Promise<string[]> {
+    return invoke('preview_rrule', { request });
+}
+
+/** 获取任务的提醒设置 */
+export async function getTaskReminders(taskId: number): Promise<TaskReminder[]> {
+    return invoke('get_task_reminders', { taskId });
+}
+
+/** 设置任务的提醒（整体替换） */
+export async function setTaskReminders(taskId: number, reminders: ReminderInput[]): Promise<TaskReminder[]> {
+    return invoke('set_task_reminders', { request: { task_id: taskId, reminders } });
+}
+
 // ==================== 写作计划 API ====================
 
 /** 获取所有计划 */
@@ -87,10 +201,20 @@ export async function updatePlanStatus(planId: number, status: string): Promise<
 }
 
 /** 更新计划基本信息 */
-export async function updatePlan(request: { id: number; name?: string; theme?: string; start_date?: string }): Promise<WritingPlan> {
+export async function updatePlan(request: UpdatePlanRequest): Promise<PlanWithDays> {
     return invoke('update_plan', { request });
 }
 
+/** 补救错过的计划日（mode 为空时使用计划自身的补救方式） */
+export async function catchUpPlan(planId: number, mode?: CatchUpMode): Promise<CatchUpResult> {
+    return invoke('catch_up_plan', { request: { plan_id: planId, mode } });
+}
+
+/** 获取计划的生命周期历史 */
+export async function getPlanEvents(planId: number): Promise<PlanEvent[]> {
+    return invoke('get_plan_events', { planId });
+}
+
 /** 删除计划 */
 export async function deletePlan(planId: number): Promise<void> {
     return invoke('delete_plan', { planId });
@@ -118,6 +242,26 @@ export async function saveWriting(request: SaveWritingRequest): Promise<Writing>
     return invoke('save_writing', { request });
 }
 
+/** 更新写作记录 */
+export async function updateWriting(request: UpdateWritingRequest): Promise<Writing> {
+    return invoke('update_writing', { request });
+}
+
+/** 删除写作记录 */
+export async function deleteWriting(id: number): Promise<void> {
+    return invoke('delete_writing', { id });
+}
+
+/** 按后端统计规则统计正文（与保存后的字数一致） */
+export async function analyzeWritingText(content: string): Promise<TextMetrics> {
+    return invoke('analyze_writing_text', { content });
+}
+
+/** 按当前统计规则重新计算所有写作记录的字数 */
+export async function recomputeWritingMetrics(): Promise<RecomputeMetricsResult> {
+    return invoke('recompute_writing_metrics');
+}
+
 /** 获取写作记录列表 */
 export async function getWritings(filter: WritingFilter = {}): Promise<Writing[]> {
     return invoke('get_writings', { filter });
@@ -128,9 +272,14 @@ export async function getWritingStats(): Promise<WritingStats> {
     return invoke('get_writing_stats');
 }
 
-/** 获取热力图数据 */
-export async function getHeatmap(): Promise<HeatmapEntry[]> {
-    return invoke('get_heatmap');
+/** 获取连续写作统计（当前连续、最长连续及其日期、所有中断） */
+export async function getStreakSummary(): Promise<StreakSummary> {
+    return invoke('get_streak_summary');
+}
+
+/** 获取热力图数据（不传请求时为最近一年的写作字数） */
+export async function getHeatmap(request?: HeatmapRequest): Promise<Heatmap> {
+    return invoke('get_heatmap', { request });
 }
 
 /** 获取写作记录详情 */
@@ -138,11 +287,158 @@ export async function getWritingDetail(id: number): Promise<Writing> {
     return invoke('get_writing_detail', { id });
 }
 
+// ==================== 写作目标 API ====================
+
+/** 获取所有写作目标 */
+export async function getWritingGoals(): Promise<WritingGoal[]> {
+    return invoke('get_writing_goals');
+}
+
+/** 设置写作目标（同周期同指标已存在时更新） */
+export async function setWritingGoal(request: SetWritingGoalRequest): Promise<WritingGoal> {
+    return invoke('set_writing_goal', { request });
+}
+
+/** 删除写作目标 */
+export async function deleteWritingGoal(id: number): Promise<void> {
+    return invoke('delete_writing_goal', { id });
+}
+
+/** 获取目标在当前周期（或指定日期所在周期）内的进度 */
+export async function getGoalProgress(date?: string): Promise<GoalProgress[]> {
+    return invoke('get_goal_progress', { date });
+}
+
+// ==================== 写作分析 API ====================
+
+/** 按日期范围和粒度统计写作数据（含与上一周期的比较） */
+export async function getWritingAnalytics(request: AnalyticsRequest): Promise<WritingAnalytics> {
+    return invoke('get_writing_analytics', { request });
+}
+
+// ==================== 任务列表 API ====================
+
+/** 获取任务列表 */
+export async function getTaskLists(includeArchived = false): Promise<TaskList[]> {
+    return invoke('get_task_lists', { includeArchived });
+}
+
+/** 创建任务列表 */
+export async function createTaskList(name: string, color?: string): Promise<TaskList> {
+    return invoke('create_task_list', { request: { name, color } });
+}
+
+/** 更新任务列表（含归档 / 取消归档） */
+export async function updateTaskList(request: { id: number; name?: string; color?: string; archived?: boolean }): Promise<TaskList> {
+    return invoke('update_task_list', { request });
+}
+
+/** 删除任务列表（任务移回收件箱） */
+export async function deleteTaskList(id: number): Promise<void> {
+    return invoke('delete_task_list', { id });
+}
+
+/** 重排任务列表 */
+export async function reorderTaskLists(listIds: number[]): Promise<TaskList[]> {
+    return invoke('reorder_task_lists', { request: { list_ids: listIds } });
+}
+
+/** 获取列表中的任务（listId 为空时返回收件箱） */
+export async function getListTasks(listId: number | null): Promise<Task[]> {
+    return invoke('get_list_tasks', { listId });
+}
+
+/** 在列表之间移动任务（listId 为空时移回收件箱） */
+export async function moveTasks(taskIds: number[], listId: number | null): Promise<void> {
+    return invoke('move_tasks', { request: { task_ids: taskIds, list_id: listId } });
+}
+
+// ==================== 标签 API ====================
+
+/** 获取所有标签 */
+export async function getTags(): Promise<Tag[]> {
+    return invoke('get_tags');
+}
+
+/** 创建标签 */
+export async function createTag(name: string, color?: string): Promise<Tag> {
+    return invoke('create_tag', { request: { name, color } });
+}
+
+/** 更新标签 */
+export async function updateTag(request: { id: number; name?: string; color?: string }): Promise<Tag> {
+    return invoke('update_tag', { request });
+}
+
+/** 删除标签（同时解除与任务、写作记录的关联） */
+export async function deleteTag(id: number): Promise<void> {
+    return invoke('delete_tag', { id });
+}
+
+/** 设置任务标签（整体替换） */
+export async function setTaskTags(taskId: number, tagIds: number[]): Promise<void> {
+    return invoke('set_task_tags', { request: { target_id: taskId, tag_ids: tagIds } });
+}
+
+/** 设置写作记录标签（整体替换） */
+export async function setWritingTags(writingId: number, tagIds: number[]): Promise<void> {
+    return invoke('set_writing_tags', { request: { target_id: writingId, tag_ids: tagIds } });
+}
+
+/** 获取标签使用统计 */
+export async function getTagStats(): Promise<TagStats[]> {
+    return invoke('get_tag_stats');
+}
+
+// ==================== 修订历史 API ====================
+
+/** 获取写作记录的所有版本（最新的在前） */
+export async function getWritingRevisions(writingId: number): Promise<WritingRevision[]> {
+    return invoke('get_writing_revisions', { writingId });
+}
+
+/** 获取单个版本 */
+export async function getWritingRevision(id: number): Promise<WritingRevision> {
+    return invoke('get_writing_revision', { id });
+}
+
+/** 比较两个版本 */
+export async function diffWritingRevisions(request: DiffRevisionsRequest): Promise<RevisionDiff> {
+    return invoke('diff_writing_revisions', { request });
+}
+
+/** 恢复旧版本为当前内容 */
+export async function restoreWritingRevision(id: number): Promise<Writing> {
+    return invoke('restore_writing_revision', { id });
+}
+
+// ==================== 写作草稿 API ====================
+
+/** 自动保存草稿（正文为空时删除草稿并返回 null） */
+export async function saveDraft(request: SaveDraftRequest): Promise<Draft | null> {
+    return invoke('save_draft', { request });
+}
+
+/** 获取所有草稿 */
+export async function getDrafts(): Promise<Draft[]> {
+    return invoke('get_drafts');
+}
+
+/** 获取计划日条目（或自由写作）对应的草稿 */
+export async function getDraft(planDayId: number | null): Promise<Draft | null> {
+    return invoke('get_draft', { planDayId });
+}
+
+/** 丢弃草稿 */
+export async function discardDraft(id: number): Promise<void> {
+    return invoke('discard_draft', { id });
+}
+
 // ==================== 批量导入 API ====================
 
 /** 批量导入任务（JSON 格式） */
-export async function importTasksJson(jsonContent: string): Promise<Task[]> {
-    return invoke('import_tasks_json', { jsonContent });
+export async function importTasksJson(jsonContent: string, listName?: string): Promise<Task[]> {
+    return invoke('import_tasks_json', { jsonContent, listName });
 }
 
 /** 导入写作计划（JSON 格式） */
@@ -154,3 +450,42 @@ export async function importPlanJson(jsonContent: string): Promise<number> {
 export async function importPlanMarkdown(mdContent: string, startDate: string): Promise<number> {
     return invoke('import_plan_markdown', { mdContent, startDate });
 }
+
+// ==================== 全文搜索 API ====================
+
+/** 搜索写作记录、每日题目和任务 */
+export async function search(request: SearchRequest): Promise<SearchResult[]> {
+    return invoke('search', { request });
+}
+
+// ==================== 应用设置 API ====================
+
+/** 获取每日写作提醒设置 */
+export async function getWritingReminderSettings(): Promise<WritingReminderSettings> {
+    return invoke('get_writing_reminder_settings');
+}
+
+/** 保存每日写作提醒设置 */
+export async function updateWritingReminderSettings(settings: WritingReminderSettings): Promise<WritingReminderSettings> {
+    return invoke('update_writing_reminder_settings', { settings });
+}
+
+/** 获取连续写作天数的统计方式 */
+export async function getStreakSettings(): Promise<StreakSettings> {
+    return invoke('get_streak_settings');
+}
+
+/** 保存连续写作天数的统计方式 */
+export async function updateStreakSettings(settings: StreakSettings): Promise<StreakSettings> {
+    return invoke('update_streak_settings', { settings });
+}
+
+/** 获取计划自动完成设置 */
+export async function getPlanCompletionSettings(): Promise<PlanCompletionSettings> {
+    return invoke('get_plan_completion_settings');
+}
+
+/** 保存计划自动完成设置 */
+export async function updatePlanCompletionSettings(settings: PlanCompletionSettings): Promise<PlanCompletionSettings> {
+    return invoke('update_plan_completion_settings', { settings });
+}
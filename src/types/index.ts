@@ -19,10 +19,102 @@ export interface Task {
   description?: string | null;
   priority: Priority;
   due_date?: string | null;
+  /** 截止时间（HH:MM） */
+  due_time?: string | null;
   repeat?: RepeatType | null;
+  /** RFC 5545 重复规则（不含 RRULE: 前缀），优先于 repeat */
+  rrule?: string | null;
+  /** 重复规则的排除日期 */
+  exdates: string[];
   completed: boolean;
   created_at?: string | null;
   updated_at?: string | null;
+  /** 清单项全部完成时自动完成任务 */
+  auto_complete: boolean;
+  item_count: number;
+  completed_item_count: number;
+  /** 清单完成比例（0 ~ 1），没有清单项时为空 */
+  progress?: number | null;
+  /** 关联的标签 ID */
+  tag_ids: number[];
+  /** 所属任务列表，为空表示位于收件箱 */
+  list_id?: number | null;
+  /** 完成时间（重复任务只在全部发生完成后才有） */
+  completed_at?: string | null;
+}
+
+/** 按截止日期分组的任务（周从周一开始） */
+export interface TaskBuckets {
+  /** 截止日期早于今天 */
+  overdue: Task[];
+  today: Task[];
+  tomorrow: Task[];
+  /** 明天之后、本周日及之前 */
+  this_week: Task[];
+  /** 本周之后 */
+  later: Task[];
+  /** 没有截止日期 */
+  no_date: Task[];
+}
+
+/** 把逾期任务顺延到今天的请求 */
+export interface RollOverTasksRequest {
+  /** 只顺延指定的任务，为空表示所有逾期任务 */
+  task_ids?: number[] | null;
+}
+
+/** 按重复方式筛选任务：none 不重复，any 任意重复，custom 使用 RRULE */
+export type RepeatFilter = 'none' | 'any' | 'daily' | 'weekly' | 'monthly' | 'custom';
+
+/** 任务排序字段（priority 升序为低 → 高；due_date 无日期的总在最后） */
+export type TaskSortKey =
+  | 'completed'
+  | 'due_date'
+  | 'priority'
+  | 'created_at'
+  | 'updated_at'
+  | 'completed_at'
+  | 'title';
+
+/** 单个排序条件 */
+export interface TaskSort {
+  key: TaskSortKey;
+  descending?: boolean;
+}
+
+/** 任务查询条件，所有筛选项之间为“且”关系 */
+export interface TaskQuery {
+  completed?: boolean | null;
+  priorities?: Priority[];
+  /** 截止日期下限（含，YYYY-MM-DD） */
+  due_from?: string | null;
+  /** 截止日期上限（含，YYYY-MM-DD） */
+  due_to?: string | null;
+  /** 指定截止日期范围时，是否同时包含没有截止日期的任务 */
+  include_undated?: boolean;
+  repeat?: RepeatFilter | null;
+  /** 标题或描述包含的文字 */
+  text?: string | null;
+  /** 包含任一指定标签 */
+  tag_ids?: number[];
+  /** 只查询指定列表中的任务 */
+  list_id?: number | null;
+  /** 只查询收件箱中未归属列表的任务 */
+  inbox?: boolean;
+  /** 排序条件，为空时使用默认排序 */
+  sort?: TaskSort[];
+  /** 每页条数，为空表示不分页 */
+  limit?: number | null;
+  offset?: number;
+}
+
+/** 任务查询结果 */
+export interface TaskPage {
+  tasks: Task[];
+  /** 符合条件的任务总数（不受分页影响） */
+  total: number;
+  offset: number;
+  limit?: number | null;
 }
 
 /** 创建任务请求 */
@@ -31,7 +123,12 @@ export interface CreateTaskRequest {
   description?: string;
   priority?: Priority;
   due_date?: string;
+  due_time?: string;
   repeat?: RepeatType;
+  rrule?: string;
+  exdates?: string[];
+  auto_complete?: boolean;
+  list_id?: number | null;
 }
 
 /** 更新任务请求 */
@@ -41,8 +138,101 @@ export interface UpdateTaskRequest {
   description?: string;
   priority?: Priority;
   due_date?: string;
+  /** 传空字符串表示清除截止时间 */
+  due_time?: string;
   repeat?: RepeatType;
+  /** 传空字符串表示清除 RRULE */
+  rrule?: string;
+  exdates?: string[];
   completed?: boolean;
+  auto_complete?: boolean;
+}
+
+/** 预览重复规则请求 */
+export interface PreviewRRuleRequest {
+  rrule: string;
+  start_date: string;
+  count?: number;
+  exdates?: string[];
+}
+
+/** 任务清单项（子任务） */
+export interface TaskItem {
+  id: number | null;
+  task_id: number;
+  title: string;
+  completed: boolean;
+  sort_order: number;
+  created_at?: string | null;
+}
+
+/** 任务 + 清单项完整视图 */
+export interface TaskDetail extends Task {
+  items: TaskItem[];
+}
+
+/** 重复任务的单次完成记录 */
+export interface TaskCompletion {
+  id: number;
+  task_id: number;
+  occurrence_date: string;
+  completed_at: string;
+}
+
+/** 任务事件类型 */
+export type TaskEventType = 'created' | 'completed' | 'reopened' | 'rescheduled';
+
+/** 任务事件 */
+export interface TaskEvent {
+  id: number;
+  task_id: number;
+  event_type: TaskEventType;
+  occurred_at: string;
+  /** 事件发生时的截止日期（完成重复任务时为本次发生的日期；改期时为新日期） */
+  due_date: string | null;
+  /** 改期前的截止日期 */
+  previous_due_date: string | null;
+}
+
+/** 任务统计请求（缺省为截至今天的最近 30 天） */
+export interface TaskStatsRequest {
+  start_date?: string;
+  end_date?: string;
+}
+
+/** 任务完成统计（已被重新打开的完成不计入） */
+export interface TaskStats {
+  start_date: string;
+  end_date: string;
+  created: number;
+  completed: number;
+  completed_on_time: number;
+  completed_overdue: number;
+  completed_without_due: number;
+  /** 按时完成率（0 ~ 1） */
+  on_time_rate: number | null;
+  /** 非重复任务从创建到完成的平均时长（小时） */
+  avg_lead_time_hours: number | null;
+  per_day: { date: string; count: number }[];
+}
+
+/** 提醒类型：截止前若干分钟 / 截止当天固定时间 */
+export type ReminderKind = 'before' | 'on_day';
+
+/** 任务提醒 */
+export interface TaskReminder {
+  id: number | null;
+  task_id: number;
+  kind: ReminderKind;
+  offset_minutes?: number | null;
+  at_time?: string | null;
+}
+
+/** 单条提醒设置 */
+export interface ReminderInput {
+  kind: ReminderKind;
+  offset_minutes?: number;
+  at_time?: string;
 }
 
 // ==================== 写作计划模块 ====================
@@ -50,6 +240,9 @@ export interface UpdateTaskRequest {
 /** 计划状态 */
 export type PlanStatus = 'active' | 'paused' | 'completed';
 
+/** 错过计划日后的补救方式：shift 整体顺延，append 移到末尾，skip 跳过 */
+export type CatchUpMode = 'shift' | 'append' | 'skip';
+
 /** 写作计划实体 */
 export interface WritingPlan {
   id: number | null;
@@ -60,6 +253,46 @@ export interface WritingPlan {
   status: PlanStatus;
   created_at?: string | null;
   completed_days: number;
+  /** 错过计划日后的补救方式 */
+  catch_up_mode: CatchUpMode;
+  /** 本次暂停的开始时间（未暂停时为空） */
+  paused_at?: string | null;
+  /** 最近一次恢复的时间 */
+  resumed_at?: string | null;
+  /** 累计暂停时长（秒，含尚未恢复的本次暂停） */
+  paused_seconds: number;
+  /** 完成时间（重新开始后清空） */
+  completed_at?: string | null;
+  /** 每周写作日（1 = 周一 … 7 = 周日），为空表示每天 */
+  writing_weekdays: number[];
+  /** 不安排写作的日期（YYYY-MM-DD） */
+  excluded_dates: string[];
+}
+
+/** 计划事件类型 */
+export type PlanEventType = 'created' | 'paused' | 'resumed' | 'completed' | 'reopened';
+
+/** 计划完成的原因 */
+export type PlanCompletionReason = 'manual' | 'all_days_written' | 'end_date_passed';
+
+/** 计划生命周期事件 */
+export interface PlanEvent {
+  id: number;
+  plan_id: number;
+  event_type: PlanEventType;
+  occurred_at: string;
+  /** 完成原因（仅完成事件） */
+  reason?: PlanCompletionReason | null;
+}
+
+/** 计划自动完成事件（plan-completed）的内容 */
+export interface PlanCompletedPayload {
+  plan_id: number;
+  plan_name: string;
+  completed_at: string;
+  reason: PlanCompletionReason;
+  completed_days: number;
+  total_days: number;
 }
 
 /** 计划每日条目 */
@@ -78,11 +311,23 @@ export interface PlanWithDays extends WritingPlan {
   completed_days: number;
 }
 
+/** 补救结果 */
+export interface CatchUpResult {
+  mode: CatchUpMode;
+  /** 今天之前未完成的条目数 */
+  missed_days: number;
+  /** 日期被改动的条目数 */
+  rescheduled_days: number;
+  plan: PlanWithDays;
+}
+
 /** 导入写作计划请求 */
 export interface ImportPlanRequest {
   name: string;
   theme?: string;
   start_date: string;
+  writing_weekdays?: number[];
+  excluded_dates?: string[];
   days: ImportPlanDayItem[];
 }
 
@@ -93,6 +338,19 @@ export interface ImportPlanDayItem {
   prompt: string;
 }
 
+/** 更新写作计划请求（只需提交有改动的字段） */
+export interface UpdatePlanRequest {
+  id: number;
+  name?: string;
+  theme?: string;
+  start_date?: string;
+  catch_up_mode?: CatchUpMode;
+  writing_weekdays?: number[];
+  excluded_dates?: string[];
+  /** 开始日期或写作日规则变化时重排未完成条目的日期 */
+  reflow_days?: boolean;
+}
+
 /** 自动生成计划请求 */
 export interface GeneratePlanRequest {
   name: string;
@@ -100,12 +358,15 @@ export interface GeneratePlanRequest {
   total_days: number;
   start_date: string;
   difficulty?: string;
+  writing_weekdays?: number[];
+  excluded_dates?: string[];
 }
 
 /** 今日写作任务 */
 export interface TodayWritingTask {
   plan_name: string;
   plan_id: number;
+  plan_day_id: number;
   day_number: number;
   title: string;
   prompt: string;
@@ -124,15 +385,109 @@ export interface Writing {
   duration_seconds: number;
   written_date: string;
   created_at?: string | null;
+  updated_at?: string | null;
+  /** 关联的标签 ID */
+  tag_ids: number[];
+  /** 文本统计（由后端计算） */
+  metrics: TextMetrics;
+}
+
+/** 文本统计：字数 = 中日韩文字数 + 拉丁词数 */
+export interface TextMetrics {
+  word_count: number;
+  cjk_chars: number;
+  latin_words: number;
+  punctuation: number;
+  paragraphs: number;
+}
+
+/** 重新统计字数的结果 */
+export interface RecomputeMetricsResult {
+  /** 检查的写作记录数 */
+  scanned: number;
+  /** 字数发生变化的记录数 */
+  changed: number;
 }
 
 /** 保存写作记录请求 */
 export interface SaveWritingRequest {
+  plan_day_id?: number | null;
+  title: string;
+  content: string;
+  /** @deprecated 字数由后端根据正文计算，传入的值会被忽略 */
+  word_count?: number;
+  duration_seconds: number;
+}
+
+/** 更新写作记录请求（修改正文时字数由后端重新计算） */
+export interface UpdateWritingRequest {
+  id: number;
+  title?: string;
+  content?: string;
+  plan_day_id?: number;
+  /** 解除与计划日条目的关联 */
+  clear_plan_day?: boolean;
+}
+
+/** 写作记录的历史版本 */
+export interface WritingRevision {
+  id: number;
+  writing_id: number;
+  /** 版本号（从 1 开始） */
+  revision_number: number;
+  title: string;
+  content: string;
+  word_count: number;
+  created_at: string;
+}
+
+/** 版本比较粒度：按行 / 按字（中文逐字，英文按词） */
+export type DiffGranularity = 'line' | 'char';
+
+/** 比较两个版本的请求（to_revision_id 为空表示与当前内容比较） */
+export interface DiffRevisionsRequest {
+  from_revision_id: number;
+  to_revision_id?: number | null;
+  granularity?: DiffGranularity;
+}
+
+/** 差异片段 */
+export interface DiffSegment {
+  op: 'equal' | 'insert' | 'delete';
+  text: string;
+}
+
+/** 版本比较结果（基于纯文本） */
+export interface RevisionDiff {
+  from_revision_id: number;
+  to_revision_id?: number | null;
+  granularity: DiffGranularity;
+  segments: DiffSegment[];
+  inserted_chars: number;
+  deleted_chars: number;
+}
+
+/** 写作草稿（自动保存） */
+export interface Draft {
+  id: number;
   plan_day_id?: number | null;
   title: string;
   content: string;
   word_count: number;
   duration_seconds: number;
+  created_at: string;
+  updated_at: string;
+  plan_id?: number | null;
+  plan_day_title?: string | null;
+  plan_name?: string | null;
+}
+
+/** 自动保存草稿请求 */
+export interface SaveDraftRequest {
+  plan_day_id?: number | null;
+  title?: string;
+  content: string;
+  duration_seconds?: number;
 }
 
 /** 写作统计摘要 */
@@ -142,15 +497,162 @@ export interface WritingStats {
   total_duration: number;
   current_streak: number;
   max_streak: number;
+  /** 最长连续的起止日期 */
+  longest_streak: StreakRange | null;
+  /** 连续天数是否只统计达成每日目标的日子 */
+  streak_requires_goal: boolean;
   avg_words_per_session: number;
   avg_duration_per_session: number;
 }
 
-/** 热力图数据点 */
+/** 一段连续写作（中间可能夹有休息日 / 冻结日） */
+export interface StreakRange {
+  start: string;
+  end: string;
+  /** 写作天数（不含跳过的休息日 / 冻结日） */
+  days: number;
+}
+
+/** 一段中断 */
+export interface StreakGap {
+  start: string;
+  end: string;
+  /** 缺勤天数（不含跳过的休息日 / 冻结日） */
+  days: number;
+}
+
+/** 连续写作统计 */
+export interface StreakSummary {
+  today: string | null;
+  /** 今天是否已写作（今天未写作不会中断当前连续） */
+  today_written: boolean;
+  current_streak: number;
+  current: StreakRange | null;
+  max_streak: number;
+  longest: StreakRange | null;
+  gaps: StreakGap[];
+}
+
+/** 分析粒度（周从周一开始） */
+export type AnalyticsGranularity = 'day' | 'week' | 'month' | 'year';
+
+/** 写作分析请求 */
+export interface AnalyticsRequest {
+  /** 开始日期（含） */
+  start_date: string;
+  /** 结束日期（含） */
+  end_date: string;
+  granularity: AnalyticsGranularity;
+}
+
+/** 一段时间内的写作汇总 */
+export interface AnalyticsTotals {
+  words: number;
+  sessions: number;
+  duration_seconds: number;
+  /** 每分钟字数（只统计有时长记录的写作） */
+  words_per_minute: number;
+  active_days: number;
+  plan_words: number;
+  plan_sessions: number;
+  free_words: number;
+  free_sessions: number;
+}
+
+/** 单个统计区间（首尾区间按请求范围截断） */
+export interface AnalyticsBucket extends AnalyticsTotals {
+  start: string;
+  end: string;
+}
+
+/** 与上一周期相比的变化百分比（上一周期为 0 时为 null） */
+export interface AnalyticsChange {
+  words: number | null;
+  sessions: number | null;
+  duration_seconds: number | null;
+  words_per_minute: number | null;
+  active_days: number | null;
+}
+
+/** 写作分析结果 */
+export interface WritingAnalytics {
+  start_date: string;
+  end_date: string;
+  granularity: AnalyticsGranularity;
+  buckets: AnalyticsBucket[];
+  totals: AnalyticsTotals;
+  /** 上一周期：紧挨在开始日期之前、天数相同的范围 */
+  previous_start_date: string;
+  previous_end_date: string;
+  previous_totals: AnalyticsTotals;
+  change: AnalyticsChange;
+}
+
+/** 目标周期（周从周一开始） */
+export type GoalPeriod = 'day' | 'week' | 'month';
+
+/** 目标指标：字数 / 写作次数 / 时长（秒） */
+export type GoalMetric = 'words' | 'sessions' | 'duration';
+
+/** 写作目标 */
+export interface WritingGoal {
+  id: number;
+  period: GoalPeriod;
+  metric: GoalMetric;
+  target: number;
+  created_at: string;
+  updated_at: string;
+}
+
+/** 设置写作目标请求（同周期同指标已存在时更新） */
+export interface SetWritingGoalRequest {
+  period: GoalPeriod;
+  metric: GoalMetric;
+  target: number;
+}
+
+/** 目标在当前周期内的进度 */
+export interface GoalProgress {
+  goal: WritingGoal;
+  period_start: string;
+  period_end: string;
+  current: number;
+  /** 完成百分比（达成后可超过 100） */
+  percent: number;
+  achieved: boolean;
+}
+
+/** 热力图指标 */
+export type HeatmapMetric = 'words' | 'sessions' | 'minutes' | 'tasks_completed';
+
+/** 热力图请求：指定 year 时取该自然年，否则取日期范围（缺省为最近一年） */
+export interface HeatmapRequest {
+  year?: number;
+  start_date?: string;
+  end_date?: string;
+  metric?: HeatmapMetric;
+  /** 强度等级数（默认 4） */
+  levels?: number;
+}
+
+/** 热力图数据点（只包含有数据的日期） */
 export interface HeatmapEntry {
   date: string;
-  word_count: number;
-  count: number;
+  value: number;
+  /** 强度等级（1 ~ levels） */
+  level: number;
+}
+
+/** 热力图 */
+export interface Heatmap {
+  start_date: string;
+  end_date: string;
+  metric: HeatmapMetric;
+  /** thresholds[i] 是 i + 1 级的最小值（按分位数计算） */
+  thresholds: number[];
+  max_value: number;
+  total: number;
+  entries: HeatmapEntry[];
 }
 
 /** 写作记录过滤条件 */
@@ -158,4 +660,101 @@ export interface WritingFilter {
   start_date?: string;
   end_date?: string;
   plan_id?: number;
+  /** 包含任一指定标签 */
+  tag_ids?: number[];
+}
+
+// ==================== 任务列表模块 ====================
+
+/** 任务列表（项目） */
+export interface TaskList {
+  id: number | null;
+  name: string;
+  color?: string | null;
+  sort_order: number;
+  /** 已归档的列表及其任务不出现在默认任务视图中 */
+  archived: boolean;
+  created_at?: string | null;
+  updated_at?: string | null;
+  task_count: number;
+  open_task_count: number;
+}
+
+// ==================== 标签模块 ====================
+
+/** 标签实体 */
+export interface Tag {
+  id: number | null;
+  name: string;
+  color?: string | null;
+  created_at?: string | null;
+}
+
+/** 标签使用统计 */
+export interface TagStats extends Tag {
+  task_count: number;
+  open_task_count: number;
+  writing_count: number;
+  writing_words: number;
+}
+
+// ==================== 全文搜索模块 ====================
+
+/** 搜索结果来源类型 */
+export type SearchKind = 'writing' | 'plan_day' | 'task';
+
+/** 搜索请求 */
+export interface SearchRequest {
+  /** 搜索词，多个词用空格分隔（需同时匹配） */
+  query: string;
+  /** 限定来源类型，为空表示全部 */
+  kinds?: SearchKind[];
+  limit?: number;
+}
+
+/**
+ * 单条搜索结果
+ *
+ * title / snippet 为 HTML 转义后的纯文本，命中的文字用 <mark></mark> 包裹，可直接用 v-html 渲染。
+ */
+export interface SearchResult {
+  kind: SearchKind;
+  id: number;
+  title: string;
+  snippet: string;
+  date?: string | null;
+  plan_id?: number | null;
+  /** 来源内的相关度得分（越小越相关），不同来源之间不可比较；结果已按来源内名次交替排列 */
+  score: number;
+}
+
+// ==================== 应用设置模块 ====================
+
+/** 每日写作提醒设置 */
+export interface WritingReminderSettings {
+  enabled: boolean;
+  /** 提醒时间（HH:MM） */
+  remind_at: string;
+  /** 是否开启"连续记录即将中断"的二次提醒 */
+  follow_up_enabled: boolean;
+  /** 二次提醒时间（HH:MM） */
+  follow_up_at: string;
+}
+
+/** 连续写作天数的统计方式 */
+export interface StreakSettings {
+  /** 只统计达成全部每日目标的日子 */
+  require_daily_goal: boolean;
+  /** 每周固定的休息日（1 = 周一 … 7 = 周日），没有写作也不中断连续 */
+  rest_weekdays: number[];
+  /** 冻结日（YYYY-MM-DD），没有写作也不中断连续 */
+  freeze_dates: string[];
+}
+
+/** 计划自动完成的条件：手动 / 全部写完 / 最后一个计划日已过 / 任一 */
+export type PlanCompletionPolicy = 'manual' | 'all_days_written' | 'end_date_passed' | 'either';
+
+/** 计划自动完成设置 */
+export interface PlanCompletionSettings {
+  policy: PlanCompletionPolicy;
 }
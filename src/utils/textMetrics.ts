@@ -0,0 +1,35 @@
+/**
+ * 字数统计，与后端 text_metrics 的规则保持一致：
+ * 中日韩文字逐字计数，拉丁字母和数字按词计数（词内的 ' ’ - 不拆分），标点不计入
+ *
+ * 只用于输入时即时显示和浏览器预览，桌面端停顿后以后端 analyze_writing_text 的结果为准。
+ * 修改规则时同步更新 textMetrics.cases.json（后端测试会逐条校验这些用例）。
+ */
+
+const CJK_RE = /[\u4E00-\u9FFF\u3400-\u4DBF\uF900-\uFAFF\u3040-\u30FF\uAC00-\uD7AF\u{20000}-\u{2EBEF}\u{30000}-\u{3134F}]/u
+const WORD_CHAR_RE = /[\p{L}\p{N}]/u
+const WORD_JOINERS = new Set(["'", '’', '-'])
+
+const isCjk = (c: string) => CJK_RE.test(c)
+const isWordChar = (c: string | undefined) => c !== undefined && WORD_CHAR_RE.test(c) && !isCjk(c)
+
+/** 统计纯文本的字数 */
+export function countWords(text: string): number {
+  const chars = Array.from(text)
+  let count = 0
+  let inWord = false
+  chars.forEach((c, i) => {
+    if (isCjk(c)) {
+      count++
+      inWord = false
+    } else if (isWordChar(c)) {
+      if (!inWord) {
+        count++
+        inWord = true
+      }
+    } else if (!(inWord && WORD_JOINERS.has(c) && isWordChar(chars[i + 1]))) {
+      inWord = false
+    }
+  })
+  return count
+}